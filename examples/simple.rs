@@ -15,7 +15,7 @@ fn main() {
     let mut glyphs = buffer_glyphs_from_bytes(ROBOTO).unwrap();
 
     // Initialize a text format
-    let format = TextFormat::new(50).color(color::WHITE);
+    let format = TextFormat::new(50.0).color(color::WHITE);
 
     // Create a pane
     let pane = Pane::new()
@@ -0,0 +1,94 @@
+use crate::math::{Rectangle, Scalar};
+use crate::Orientation;
+
+/// A condition on the size of a `Pane`'s rectangle
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Condition<S>
+where
+    S: Scalar,
+{
+    /// The width is less than the value
+    NarrowerThan(S),
+    /// The width is greater than the value
+    WiderThan(S),
+    /// The height is less than the value
+    ShorterThan(S),
+    /// The height is greater than the value
+    TallerThan(S),
+    /// The width divided by the height is less than the value
+    AspectRatioBelow(S),
+    /// The width divided by the height is greater than the value
+    AspectRatioAbove(S),
+}
+
+impl<S> Condition<S>
+where
+    S: Scalar,
+{
+    /// Check if a rectangle meets the condition
+    pub fn is_met<R>(self, rect: R) -> bool
+    where
+        R: Rectangle<Scalar = S>,
+    {
+        use self::Condition::*;
+        match self {
+            NarrowerThan(width) => rect.width() < width,
+            WiderThan(width) => rect.width() > width,
+            ShorterThan(height) => rect.height() < height,
+            TallerThan(height) => rect.height() > height,
+            AspectRatioBelow(ratio) => rect.width() < rect.height() * ratio,
+            AspectRatioAbove(ratio) => rect.width() > rect.height() * ratio,
+        }
+    }
+}
+
+/// A rule which changes the layout of a `Pane`'s children
+/// when the `Pane`'s rectangle meets some `Condition`
+///
+/// Breakpoints are checked every time a `Pane`'s inner rectangles
+/// are updated, so resizing a `Pane` automatically rearranges it.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Breakpoint<S>
+where
+    S: Scalar,
+{
+    /// The condition which activates the breakpoint
+    pub condition: Condition<S>,
+    /// The split orientation to use while the breakpoint is active
+    pub orientation: Option<Orientation>,
+    /// The indices of the children to hide while the breakpoint is active
+    pub hidden: Vec<usize>,
+}
+
+impl<S> From<Condition<S>> for Breakpoint<S>
+where
+    S: Scalar,
+{
+    fn from(condition: Condition<S>) -> Self {
+        Breakpoint::new(condition)
+    }
+}
+
+impl<S> Breakpoint<S>
+where
+    S: Scalar,
+{
+    /// Create a new `Breakpoint` that does nothing when the condition is met
+    pub fn new(condition: Condition<S>) -> Self {
+        Breakpoint {
+            condition,
+            orientation: None,
+            hidden: Vec::new(),
+        }
+    }
+    /// Set the split orientation to use when the condition is met
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+    /// Hide the child at the given index when the condition is met
+    pub fn hide(mut self, index: usize) -> Self {
+        self.hidden.push(index);
+        self
+    }
+}
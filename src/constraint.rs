@@ -0,0 +1,385 @@
+#[cfg(feature = "constraints")]
+use std::collections::HashMap;
+
+use crate::math::Scalar;
+#[cfg(feature = "constraints")]
+use crate::math::{Rectangle, Vector2};
+
+/// An edge or dimension of a `Pane`'s rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edge {
+    /// The left x
+    Left,
+    /// The right x
+    Right,
+    /// The top y
+    Top,
+    /// The bottom y
+    Bottom,
+    /// The width
+    Width,
+    /// The height
+    Height,
+    /// The x of the center
+    CenterX,
+    /// The y of the center
+    CenterY,
+}
+
+/// A relation between the two sides of a `Constraint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Relation {
+    /// The sides are equal
+    Equal,
+    /// The left side is less than or equal to the right side
+    AtMost,
+    /// The left side is greater than or equal to the right side
+    AtLeast,
+}
+
+/// How strongly a `Constraint` should be enforced
+///
+/// When constraints conflict, weaker ones are violated first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strength {
+    /// The constraint must be satisfied
+    Required,
+    /// Strong preference
+    Strong,
+    /// Medium preference
+    Medium,
+    /// Weak preference
+    Weak,
+}
+
+/// A linear constraint on the edge of a named `Pane`
+///
+/// A constraint has the form `pane.edge <relation> target.edge * multiplier + constant`.
+/// If there is no target, it has the form `pane.edge <relation> constant`.
+/// Panes are referred to by the names given to them with `Pane::with_panes`.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Constraint<S>
+where
+    S: Scalar,
+{
+    /// The name of the constrained `Pane`
+    pub pane: String,
+    /// The constrained edge
+    pub edge: Edge,
+    /// The relation between the two sides
+    pub relation: Relation,
+    /// The name and edge of the `Pane` on the other side
+    pub target: Option<(String, Edge)>,
+    /// The multiplier of the target's edge
+    pub multiplier: S,
+    /// The constant added to the other side
+    pub constant: S,
+    /// The strength
+    pub strength: Strength,
+}
+
+impl<S> Constraint<S>
+where
+    S: Scalar,
+{
+    /// Create a new required `Constraint` which constrains the given edge of the named `Pane` to `0`
+    pub fn new<T>(pane: T, edge: Edge) -> Self
+    where
+        T: Into<String>,
+    {
+        Constraint {
+            pane: pane.into(),
+            edge,
+            relation: Relation::Equal,
+            target: None,
+            multiplier: S::ONE,
+            constant: S::ZERO,
+            strength: Strength::Required,
+        }
+    }
+    /// Constrain the edge to be equal to the given edge of another named `Pane`
+    pub fn equal_to<T>(self, pane: T, edge: Edge) -> Self
+    where
+        T: Into<String>,
+    {
+        self.relative_to(Relation::Equal, pane, edge)
+    }
+    /// Constrain the edge to be at most the given edge of another named `Pane`
+    pub fn at_most<T>(self, pane: T, edge: Edge) -> Self
+    where
+        T: Into<String>,
+    {
+        self.relative_to(Relation::AtMost, pane, edge)
+    }
+    /// Constrain the edge to be at least the given edge of another named `Pane`
+    pub fn at_least<T>(self, pane: T, edge: Edge) -> Self
+    where
+        T: Into<String>,
+    {
+        self.relative_to(Relation::AtLeast, pane, edge)
+    }
+    fn relative_to<T>(mut self, relation: Relation, pane: T, edge: Edge) -> Self
+    where
+        T: Into<String>,
+    {
+        self.relation = relation;
+        self.target = Some((pane.into(), edge));
+        self
+    }
+    /// Set the relation
+    pub fn relation(mut self, relation: Relation) -> Self {
+        self.relation = relation;
+        self
+    }
+    /// Set the multiplier of the target's edge
+    pub fn times(mut self, multiplier: S) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+    /// Set the constant added to the other side
+    pub fn plus(mut self, constant: S) -> Self {
+        self.constant = constant;
+        self
+    }
+    /// Set the strength
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+#[cfg(feature = "constraints")]
+fn term(variable: cassowary::Variable, coefficient: f64) -> cassowary::Term {
+    cassowary::Term {
+        variable,
+        coefficient,
+    }
+}
+
+#[cfg(feature = "constraints")]
+struct Variables {
+    left: cassowary::Variable,
+    top: cassowary::Variable,
+    width: cassowary::Variable,
+    height: cassowary::Variable,
+}
+
+#[cfg(feature = "constraints")]
+impl Variables {
+    fn new() -> Self {
+        use cassowary::Variable;
+        Variables {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+    fn edge(&self, edge: Edge) -> cassowary::Expression {
+        use cassowary::Expression;
+        let terms = match edge {
+            Edge::Left => vec![term(self.left, 1.0)],
+            Edge::Top => vec![term(self.top, 1.0)],
+            Edge::Width => vec![term(self.width, 1.0)],
+            Edge::Height => vec![term(self.height, 1.0)],
+            Edge::Right => vec![term(self.left, 1.0), term(self.width, 1.0)],
+            Edge::Bottom => vec![term(self.top, 1.0), term(self.height, 1.0)],
+            Edge::CenterX => vec![term(self.left, 1.0), term(self.width, 0.5)],
+            Edge::CenterY => vec![term(self.top, 1.0), term(self.height, 0.5)],
+        };
+        Expression::new(terms, 0.0)
+    }
+}
+
+/// A group of sibling `Pane`s which are solved together
+///
+/// Siblings are kept side by side in the order they are drawn,
+/// and inside their parent's rectangle.
+#[cfg(feature = "constraints")]
+pub(crate) struct SiblingGroup<R> {
+    /// The index of the parent's rectangle, if it is also solved
+    pub parent: Option<usize>,
+    /// The parent's rectangle
+    pub parent_rect: R,
+    /// Whether the siblings are side by side horizontally rather than vertically
+    pub horizontal: bool,
+    /// The indices of the siblings' rectangles in the order they are drawn
+    pub children: Vec<usize>,
+}
+
+/// Get the value of an edge of a rectangle
+#[cfg(feature = "constraints")]
+fn edge_value<R>(rect: R, edge: Edge) -> f64
+where
+    R: Rectangle<Scalar = f64>,
+{
+    match edge {
+        Edge::Left => rect.left(),
+        Edge::Right => rect.right(),
+        Edge::Top => rect.top(),
+        Edge::Bottom => rect.bottom(),
+        Edge::Width => rect.width(),
+        Edge::Height => rect.height(),
+        Edge::CenterX => rect.center().x(),
+        Edge::CenterY => rect.center().y(),
+    }
+}
+
+/// Solve a set of constraints on some rectangles
+///
+/// The given rectangles are the ones calculated from weights, which are weakly
+/// preferred. Names map to indices of the rectangles. Returns the solved
+/// rectangles and the constraints which were skipped.
+#[cfg(feature = "constraints")]
+pub(crate) fn solve<R>(
+    constraints: &[Constraint<f64>],
+    rects: &[R],
+    names: &HashMap<String, usize>,
+    groups: &[SiblingGroup<R>],
+) -> (Vec<R>, Vec<Constraint<f64>>)
+where
+    R: Rectangle<Scalar = f64>,
+{
+    use cassowary::{
+        strength::{MEDIUM, REQUIRED, STRONG, WEAK},
+        Expression, RelationalOperator, Solver,
+    };
+    let mut solver = Solver::new();
+    let vars: Vec<Variables> = rects.iter().map(|_| Variables::new()).collect();
+    // Prefer the weighted layout, more weakly than any user constraint,
+    // and keep sizes positive
+    let stay = WEAK / 2.0;
+    for (var, rect) in vars.iter().zip(rects) {
+        let stays = [
+            (var.left, rect.left()),
+            (var.top, rect.top()),
+            (var.width, rect.width()),
+            (var.height, rect.height()),
+        ];
+        for &(v, value) in &stays {
+            let expr = Expression::new(vec![term(v, 1.0)], -value);
+            solver
+                .add_constraint(cassowary::Constraint::new(
+                    expr,
+                    RelationalOperator::Equal,
+                    stay,
+                ))
+                .expect("weighted layout constraint");
+        }
+        for &v in &[var.width, var.height] {
+            let expr = Expression::new(vec![term(v, 1.0)], 0.0);
+            solver
+                .add_constraint(cassowary::Constraint::new(
+                    expr,
+                    RelationalOperator::GreaterOrEqual,
+                    REQUIRED,
+                ))
+                .expect("positive size constraint");
+        }
+    }
+    // Keep siblings side by side and inside their parents, with the same
+    // spacing and insets as the weighted layout. Siblings prefer to fill
+    // their parents across the split, unless a user constraint says otherwise.
+    let fill = (stay + WEAK) / 2.0;
+    for group in groups {
+        let (first, last) = match (group.children.first(), group.children.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => continue,
+        };
+        let (start, end, cross_start, cross_end) = if group.horizontal {
+            (Edge::Left, Edge::Right, Edge::Top, Edge::Bottom)
+        } else {
+            (Edge::Top, Edge::Bottom, Edge::Left, Edge::Right)
+        };
+        let parent_edge = |edge: Edge| match group.parent {
+            Some(parent) => vars[parent].edge(edge),
+            None => Expression::from_constant(edge_value(group.parent_rect, edge)),
+        };
+        let inset = |child: usize, edge: Edge| {
+            edge_value(rects[child], edge) - edge_value(group.parent_rect, edge)
+        };
+        let mut relations = vec![
+            (
+                vars[first].edge(start) - parent_edge(start) - inset(first, start),
+                RelationalOperator::Equal,
+                REQUIRED,
+            ),
+            (
+                vars[last].edge(end) - parent_edge(end) - inset(last, end),
+                RelationalOperator::Equal,
+                REQUIRED,
+            ),
+        ];
+        for pair in group.children.windows(2) {
+            let spacing = edge_value(rects[pair[1]], start) - edge_value(rects[pair[0]], end);
+            relations.push((
+                vars[pair[1]].edge(start) - vars[pair[0]].edge(end) - spacing,
+                RelationalOperator::Equal,
+                REQUIRED,
+            ));
+        }
+        for &child in &group.children {
+            for &(edge, op) in &[
+                (cross_start, RelationalOperator::GreaterOrEqual),
+                (cross_end, RelationalOperator::LessOrEqual),
+            ] {
+                let expr = vars[child].edge(edge) - parent_edge(edge) - inset(child, edge);
+                relations.push((expr.clone(), op, REQUIRED));
+                relations.push((expr, RelationalOperator::Equal, fill));
+            }
+        }
+        for (expr, op, strength) in relations {
+            // These only conflict with the positive sizes in layouts which are
+            // already too small for their margins, where they are left out
+            let _ = solver.add_constraint(cassowary::Constraint::new(expr, op, strength));
+        }
+    }
+    // Add the user constraints, skipping any that cannot be satisfied
+    let mut skipped = Vec::new();
+    for constraint in constraints {
+        let lhs = if let Some(&i) = names.get(&constraint.pane) {
+            vars[i].edge(constraint.edge)
+        } else {
+            skipped.push(constraint.clone());
+            continue;
+        };
+        let rhs = match constraint.target {
+            Some((ref target, edge)) => {
+                if let Some(&i) = names.get(target) {
+                    vars[i].edge(edge) * constraint.multiplier + constraint.constant
+                } else {
+                    skipped.push(constraint.clone());
+                    continue;
+                }
+            }
+            None => Expression::from_constant(constraint.constant),
+        };
+        let op = match constraint.relation {
+            Relation::Equal => RelationalOperator::Equal,
+            Relation::AtMost => RelationalOperator::LessOrEqual,
+            Relation::AtLeast => RelationalOperator::GreaterOrEqual,
+        };
+        let strength = match constraint.strength {
+            Strength::Required => REQUIRED,
+            Strength::Strong => STRONG,
+            Strength::Medium => MEDIUM,
+            Strength::Weak => WEAK,
+        };
+        if solver
+            .add_constraint(cassowary::Constraint::new(lhs - rhs, op, strength))
+            .is_err()
+        {
+            skipped.push(constraint.clone());
+        }
+    }
+    let solved = vars
+        .iter()
+        .map(|var| {
+            R::new(
+                R::Vector::new(solver.get_value(var.left), solver.get_value(var.top)),
+                R::Vector::new(solver.get_value(var.width), solver.get_value(var.height)),
+            )
+        })
+        .collect();
+    (solved, skipped)
+}
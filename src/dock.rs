@@ -0,0 +1,102 @@
+use crate::math::{Rectangle, Vector2, ZeroOneTwo};
+use crate::Orientation;
+
+/// A region of a `Pane` onto which another `Pane` can be docked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DropZone {
+    /// Split the `Pane` and put the docked `Pane` on the left
+    Left,
+    /// Split the `Pane` and put the docked `Pane` on the right
+    Right,
+    /// Split the `Pane` and put the docked `Pane` on top
+    Top,
+    /// Split the `Pane` and put the docked `Pane` on the bottom
+    Bottom,
+    /// Add the docked `Pane` as a tab alongside the `Pane`
+    Center,
+}
+
+impl DropZone {
+    /// Get the drop zone of a rectangle that a point is in
+    ///
+    /// Points in the middle half of the rectangle are in the center zone.
+    /// Other points are in the zone of the nearest edge.
+    pub fn at<R, V>(rect: R, pos: V) -> DropZone
+    where
+        R: Rectangle,
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        let quarter = R::Scalar::ONE / R::Scalar::from(4u32);
+        let u = (pos.x() - rect.left()) / rect.width();
+        let v = (pos.y() - rect.top()) / rect.height();
+        let three_quarters = R::Scalar::ONE - quarter;
+        if u >= quarter && u <= three_quarters && v >= quarter && v <= three_quarters {
+            return DropZone::Center;
+        }
+        let distances = [
+            (u, DropZone::Left),
+            (R::Scalar::ONE - u, DropZone::Right),
+            (v, DropZone::Top),
+            (R::Scalar::ONE - v, DropZone::Bottom),
+        ];
+        distances
+            .iter()
+            .fold(distances[0], |nearest, &zone| {
+                if zone.0 < nearest.0 {
+                    zone
+                } else {
+                    nearest
+                }
+            })
+            .1
+    }
+    /// Get the orientation of the split this zone creates, if any
+    pub fn orientation(self) -> Option<Orientation> {
+        match self {
+            DropZone::Left | DropZone::Right => Some(Orientation::Horizontal),
+            DropZone::Top | DropZone::Bottom => Some(Orientation::Vertical),
+            DropZone::Center => None,
+        }
+    }
+    /// Check if the docked `Pane` goes before the target in its split
+    pub(crate) fn is_before(self) -> bool {
+        match self {
+            DropZone::Left | DropZone::Top => true,
+            DropZone::Right | DropZone::Bottom | DropZone::Center => false,
+        }
+    }
+    /// Get the part of a rectangle that a `Pane` docked in this zone would cover
+    pub fn preview<R>(self, rect: R) -> R
+    where
+        R: Rectangle,
+    {
+        let half = rect.size().div(R::Scalar::TWO);
+        match self {
+            DropZone::Left => R::new(rect.top_left(), R::Vector::new(half.x(), rect.height())),
+            DropZone::Right => R::new(
+                R::Vector::new(rect.left() + half.x(), rect.top()),
+                R::Vector::new(half.x(), rect.height()),
+            ),
+            DropZone::Top => R::new(rect.top_left(), R::Vector::new(rect.width(), half.y())),
+            DropZone::Bottom => R::new(
+                R::Vector::new(rect.left(), rect.top() + half.y()),
+                R::Vector::new(rect.width(), half.y()),
+            ),
+            DropZone::Center => rect,
+        }
+    }
+}
+
+/// A place to dock a `Pane`, as found by `Pane::dock_target`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockTarget<R = [f64; 4]>
+where
+    R: Rectangle,
+{
+    /// The path of child indices to the target `Pane`
+    pub path: Vec<usize>,
+    /// The zone of the target `Pane`
+    pub zone: DropZone,
+    /// The rectangle that the docked `Pane` would cover, for drawing a preview
+    pub rect: R,
+}
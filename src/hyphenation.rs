@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+/// A hyphenator which uses Liang's algorithm with TeX-style hyphenation patterns
+///
+/// Patterns for many languages are available from the `hyph-utf8` project.
+/// Patterns are letters interspersed with digits, such as `a1b` or `.ach4`,
+/// where odd digits mark hyphenation points and `.` marks the edge of a word.
+#[derive(Debug, Clone, Default)]
+pub struct Hyphenator {
+    patterns: HashMap<String, Vec<u8>>,
+    exceptions: HashMap<String, Vec<usize>>,
+    max_pattern_len: usize,
+    left_min: usize,
+    right_min: usize,
+}
+
+impl Hyphenator {
+    /// Create a new `Hyphenator` from whitespace-separated patterns
+    ///
+    /// By default, words are not hyphenated within 2 characters
+    /// of their start or 3 characters of their end.
+    pub fn new(patterns: &str) -> Self {
+        let mut hyphenator = Hyphenator {
+            left_min: 2,
+            right_min: 3,
+            ..Hyphenator::default()
+        };
+        for pattern in patterns.split_whitespace() {
+            let mut letters = String::new();
+            let mut values = vec![0];
+            for c in pattern.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    *values.last_mut().unwrap() = digit as u8;
+                } else {
+                    letters.push(c);
+                    values.push(0);
+                }
+            }
+            hyphenator.max_pattern_len = hyphenator.max_pattern_len.max(letters.chars().count());
+            hyphenator.patterns.insert(letters, values);
+        }
+        hyphenator
+    }
+    /// Add exceptions to the patterns, with hyphens marking hyphenation points
+    /// such as `ta-ble`
+    pub fn with_exceptions(mut self, exceptions: &str) -> Self {
+        for exception in exceptions.split_whitespace() {
+            let mut points = Vec::new();
+            let mut word = String::new();
+            for c in exception.chars() {
+                if c == '-' {
+                    points.push(word.chars().count());
+                } else {
+                    word.push(c);
+                }
+            }
+            self.exceptions.insert(word.to_lowercase(), points);
+        }
+        self
+    }
+    /// Set the minimum number of characters before and after a hyphenation point
+    pub fn with_min_lengths(mut self, left_min: usize, right_min: usize) -> Self {
+        self.left_min = left_min;
+        self.right_min = right_min;
+        self
+    }
+    /// Get the byte indices in a word at which it may be hyphenated
+    pub fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let lower = word.to_lowercase();
+        let char_count = word.chars().count();
+        // Lowercasing can change the number of characters, in which case
+        // the hyphenation points would not line up with the word
+        if lower.chars().count() != char_count {
+            return Vec::new();
+        }
+        let char_points: Vec<usize> = if let Some(points) = self.exceptions.get(&lower) {
+            points.clone()
+        } else {
+            let chars: Vec<char> = format!(".{}.", lower).chars().collect();
+            let mut values = vec![0u8; chars.len() + 1];
+            for i in 0..chars.len() {
+                for j in (i + 1)..=(i + self.max_pattern_len).min(chars.len()) {
+                    let part: String = chars[i..j].iter().collect();
+                    if let Some(pattern) = self.patterns.get(&part) {
+                        for (k, &value) in pattern.iter().enumerate() {
+                            values[i + k] = values[i + k].max(value);
+                        }
+                    }
+                }
+            }
+            // A break before the word's nth character is after the nth
+            // character of the dotted word
+            (1..char_count)
+                .filter(|&n| values[n + 1] % 2 == 1)
+                .collect()
+        };
+        let byte_indices: Vec<usize> = word.char_indices().map(|(i, _)| i).collect();
+        char_points
+            .into_iter()
+            .filter(|&n| n >= self.left_min && n + self.right_min <= char_count)
+            .filter_map(|n| byte_indices.get(n).cloned())
+            .collect()
+    }
+}
@@ -5,28 +5,67 @@
 //!
 //! The `graphics` feature, which is on by default, allow the direct rendering of a `Pane` with the `piston2d-graphics` crate.
 
+mod breakpoint;
+mod constraint;
+mod dock;
+mod hyphenation;
 pub mod math;
+mod popup;
+mod style;
 mod text;
+#[cfg(feature = "graphics")]
+mod texture;
+mod theme;
+mod tween;
 /// A prelud containing commonly used items in `Pane`
 pub mod prelude {
+    pub use crate::breakpoint::{Breakpoint, Condition};
     pub use crate::color;
+    pub use crate::constraint::{Constraint, Edge, Relation, Strength};
+    pub use crate::dock::{DockTarget, DropZone};
     pub use crate::math::{Rectangle, Scalar, Vector2};
+    pub use crate::popup::{Anchor, Hit, Popup, Side};
+    pub use crate::style::{Background, Border, ImageFill, ImageMode, Overflow, Shadow, Splitter};
     #[cfg(feature = "graphics")]
     pub use crate::text::justified_text;
-    pub use crate::text::{Justification, TextFormat};
+    pub use crate::text::{
+        Justification, TabStops, TextFormat, TextOverflow, TextStyle, VerticalAlignment,
+        WhiteSpace, WordBreak,
+    };
+    #[cfg(feature = "graphics")]
+    pub use crate::texture::{TextureCache, TextureSource};
+    pub use crate::theme::{Selector, Style, Theme};
+    pub use crate::tween::{Easing, Layout, Tween};
     pub use crate::Contents;
+    pub use crate::Direction;
     pub use crate::Orientation;
     pub use crate::Pane;
 }
 
-use std::{collections::HashMap, ops};
+use std::{cmp::Ordering, collections::HashMap, ops};
 
 #[cfg(feature = "graphics")]
-use graphics::{character::CharacterCache, math::Matrix2d, rectangle, Graphics, ImageSize};
+use graphics::{
+    character::CharacterCache, draw_state::DrawState, math::Matrix2d, Context, Graphics, ImageSize,
+};
 
 use crate::math::{Rectangle, Scalar, Vector2, ZeroOneTwo};
 
+pub use crate::breakpoint::*;
+pub use crate::constraint::*;
+pub use crate::dock::*;
+pub use crate::hyphenation::*;
+pub use crate::popup::*;
+pub use crate::style::*;
+#[cfg(feature = "graphics")]
+use crate::text::draw_words;
 pub use crate::text::*;
+#[cfg(feature = "graphics")]
+use crate::texture::NoTextures;
+#[cfg(feature = "graphics")]
+pub use crate::texture::{TextureCache, TextureSource};
+pub use crate::theme::*;
+pub use crate::tween::*;
 
 /// Possible content of a `Pane`
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -35,14 +74,20 @@ where
     S: Scalar,
 {
     /// Text with some format
+    ///
+    /// The format is used as is, regardless of the text styles
+    /// of the `Pane` and its ancestors.
     Text(String, TextFormat<S>),
+    /// Text with a partial style whose unset fields are inherited
+    /// from the text styles of the `Pane` and its ancestors
+    StyledText(String, TextStyle<S>),
 }
 
 impl<S> Contents<S>
 where
     S: Scalar,
 {
-    /// Create a new `Context::Text`
+    /// Create a new `Contents::Text`
     #[allow(clippy::needless_pass_by_value)]
     pub fn text<T, F>(text: T, format: F) -> Self
     where
@@ -51,30 +96,72 @@ where
     {
         Contents::Text(text.to_string(), format.into())
     }
+    /// Create a new `Contents::StyledText`
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn styled_text<T>(text: T, style: TextStyle<S>) -> Self
+    where
+        T: ToString,
+    {
+        Contents::StyledText(text.to_string(), style)
+    }
+    /// Get the text
+    pub fn text_str(&self) -> &str {
+        match self {
+            Contents::Text(text, _) | Contents::StyledText(text, _) => text,
+        }
+    }
+    /// Get the format of the text given the style of the `Pane` and its ancestors
+    ///
+    /// Fields set by the contents take priority over inherited ones.
+    pub fn format(&self, inherited: TextStyle<S>) -> TextFormat<S> {
+        match self {
+            Contents::Text(_, format) => *format,
+            Contents::StyledText(_, style) => style.inherit(inherited).format(),
+        }
+    }
 }
 
 /// An orientation for splitting a `Pane`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum Orientation {
     /// Split the pane horizantally
     Horizontal,
     /// Split the pane vertically
+    #[default]
     Vertical,
 }
 
-impl Default for Orientation {
-    fn default() -> Self {
-        Orientation::Vertical
-    }
+/// A direction in which a `Pane`'s children are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Direction {
+    /// The first child is on the left or top
+    #[default]
+    Forward,
+    /// The first child is on the right or bottom
+    ///
+    /// This is useful for mirroring horizontal layouts in right-to-left locales
+    Reverse,
 }
 
 impl Orientation {
-    fn split_rect<R, W>(self, margin: R::Scalar, rect: R, weights: W) -> Vec<R>
+    fn split_rect<R, W>(
+        self,
+        direction: Direction,
+        margin: R::Scalar,
+        rect: R,
+        weights: W,
+    ) -> Vec<R>
     where
         R: Rectangle,
         W: IntoIterator<Item = R::Scalar>,
     {
-        let weights: Vec<R::Scalar> = weights.into_iter().collect();
+        let mut weights: Vec<R::Scalar> = weights.into_iter().collect();
+        if direction == Direction::Reverse {
+            weights.reverse();
+            let mut rects = self.split_rect(Direction::Forward, margin, rect, weights);
+            rects.reverse();
+            return rects;
+        }
         let sum: R::Scalar = weights
             .iter()
             .cloned()
@@ -129,11 +216,33 @@ where
 {
     contents: Option<Contents<R::Scalar>>,
     orientation: Orientation,
+    direction: Direction,
     margin: R::Scalar,
     names: HashMap<String, usize>,
     rect: R,
     children: Vec<(R::Scalar, Pane<R>)>,
     color: Color,
+    pixel_snap: Option<R::Scalar>,
+    breakpoints: Vec<Breakpoint<R::Scalar>>,
+    hidden: bool,
+    constraints: Vec<Constraint<R::Scalar>>,
+    solved_rect: Option<R>,
+    solved_from: Option<R>,
+    skipped_constraints: Vec<Constraint<R::Scalar>>,
+    border: Option<Border<R::Scalar>>,
+    corner_radius: R::Scalar,
+    background: Option<Background<R::Scalar>>,
+    shadow: Option<Shadow<R::Scalar>>,
+    overflow: Overflow,
+    text_style: TextStyle<R::Scalar>,
+    fitted_font_size: Option<f32>,
+    classes: Vec<String>,
+    layer: Option<i32>,
+    popups: Vec<Popup<R>>,
+    active_tab: Option<usize>,
+    splitter: Option<Splitter<R::Scalar>>,
+    hovered_splitter: Option<usize>,
+    wrapper: bool,
 }
 
 impl<R> Default for Pane<R>
@@ -154,6 +263,7 @@ where
         Pane {
             contents: None,
             orientation: Orientation::default(),
+            direction: Direction::default(),
             margin: R::Scalar::ZERO,
             names: HashMap::new(),
             children: Vec::new(),
@@ -162,6 +272,27 @@ where
                 R::Vector::new(R::Scalar::ONE, R::Scalar::ONE),
             ),
             color: color::TRANSPARENT,
+            pixel_snap: None,
+            breakpoints: Vec::new(),
+            hidden: false,
+            constraints: Vec::new(),
+            solved_rect: None,
+            solved_from: None,
+            skipped_constraints: Vec::new(),
+            border: None,
+            corner_radius: R::Scalar::ZERO,
+            background: None,
+            shadow: None,
+            overflow: Overflow::default(),
+            text_style: TextStyle::new(),
+            fitted_font_size: None,
+            classes: Vec::new(),
+            layer: None,
+            popups: Vec::new(),
+            active_tab: None,
+            splitter: None,
+            hovered_splitter: None,
+            wrapper: false,
         }
     }
     /// Immutable iterate over the `Pane`'s children
@@ -172,6 +303,28 @@ where
     pub fn children_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Pane<R>> {
         self.children.iter_mut().map(|(_, pane)| pane)
     }
+    /// Find a `Pane` anywhere in the `Pane`'s tree by the name it was given
+    ///
+    /// If multiple `Pane`s have the same name, the `Pane`'s own children are
+    /// checked first, then each child's tree is searched in order.
+    pub fn find(&self, name: &str) -> Option<&Pane<R>> {
+        if let Some(&index) = self.names.get(name) {
+            return Some(&self.children[index].1);
+        }
+        self.children().filter_map(|pane| pane.find(name)).next()
+    }
+    /// Mutably find a `Pane` anywhere in the `Pane`'s tree by the name it was given
+    ///
+    /// If multiple `Pane`s have the same name, the `Pane`'s own children are
+    /// checked first, then each child's tree is searched in order.
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut Pane<R>> {
+        if let Some(&index) = self.names.get(name) {
+            return Some(&mut self.children[index].1);
+        }
+        self.children_mut()
+            .filter_map(|pane| pane.find_mut(name))
+            .next()
+    }
     /// Get the `Pane`'s contents
     pub fn contents(&self) -> Option<&Contents<R::Scalar>> {
         self.contents.as_ref()
@@ -179,13 +332,19 @@ where
     /// Change the `Pane`'s contents
     pub fn with_contents(mut self, contents: Contents<R::Scalar>) -> Self {
         self.contents = Some(contents);
+        self.fitted_font_size = None;
         self
     }
     /// Remove the `Pane`'s contents
     pub fn with_no_contents(mut self) -> Self {
         self.contents = None;
+        self.fitted_font_size = None;
         self
     }
+    /// Get the font size that the `Pane`'s text was fitted to by `Pane::fit_text`
+    pub fn fitted_font_size(&self) -> Option<f32> {
+        self.fitted_font_size
+    }
     /// Get the `Pane`'s rectangle
     pub fn rect(&self) -> R {
         self.rect
@@ -260,6 +419,83 @@ where
         self.update_rects();
         self
     }
+    /// Get the split orientation of the `Pane`'s children after
+    /// applying any active `Breakpoint`s
+    pub fn layout_orientation(&self) -> Orientation {
+        self.breakpoints
+            .iter()
+            .filter(|bp| bp.condition.is_met(self.rect))
+            .filter_map(|bp| bp.orientation)
+            .last()
+            .unwrap_or(self.orientation)
+    }
+    /// Get the `Pane`'s breakpoints
+    pub fn breakpoints(&self) -> &[Breakpoint<R::Scalar>] {
+        &self.breakpoints
+    }
+    /// Add a breakpoint to the `Pane`
+    ///
+    /// If multiple active breakpoints set the orientation,
+    /// the one added last is used.
+    pub fn with_breakpoint<B>(mut self, breakpoint: B) -> Self
+    where
+        B: Into<Breakpoint<R::Scalar>>,
+    {
+        self.breakpoints.push(breakpoint.into());
+        self.update_rects();
+        self
+    }
+    /// Get the `Pane`'s layout constraints
+    pub fn constraints(&self) -> &[Constraint<R::Scalar>] {
+        &self.constraints
+    }
+    /// Add a layout constraint to the `Pane`
+    ///
+    /// Constraints can refer to any named `Pane` in the tree and
+    /// take effect when `Pane::solve_constraints` is called on
+    /// an ancestor of all the `Pane`s involved.
+    pub fn with_constraint(mut self, constraint: Constraint<R::Scalar>) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+    /// Get the constraints which were skipped the last time `Pane::solve_constraints`
+    /// was called on the `Pane`
+    ///
+    /// Constraints are skipped if they refer to a `Pane` which cannot be found
+    /// or if they cannot be satisfied along with the required constraints before them.
+    pub fn skipped_constraints(&self) -> &[Constraint<R::Scalar>] {
+        &self.skipped_constraints
+    }
+    #[cfg(feature = "constraints")]
+    fn all_constraints(&self) -> Vec<Constraint<R::Scalar>> {
+        let mut constraints = self.constraints.clone();
+        for pane in self.children() {
+            constraints.extend(pane.all_constraints());
+        }
+        constraints
+    }
+    #[cfg(feature = "constraints")]
+    fn clear_solved_rects(&mut self) {
+        self.solved_rect = None;
+        self.solved_from = None;
+        for pane in self.children_mut() {
+            pane.clear_solved_rects();
+        }
+    }
+    /// Check if the `Pane` has been hidden by one of its parent's breakpoints
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+    /// Get the direction in which the `Pane`'s children are ordered
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+    /// Set the direction in which the `Pane`'s children are ordered
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self.update_rects();
+        self
+    }
     /// Get the `Pane`'s color
     pub fn color(&self) -> Color {
         self.color
@@ -269,6 +505,636 @@ where
         self.color = color;
         self
     }
+    /// Get the `Pane`'s background
+    pub fn background(&self) -> Option<&Background<R::Scalar>> {
+        self.background.as_ref()
+    }
+    /// Set the `Pane`'s background
+    ///
+    /// The background is drawn over the `Pane`'s color
+    pub fn with_background(mut self, background: Background<R::Scalar>) -> Self {
+        self.background = Some(background);
+        self
+    }
+    /// Remove the `Pane`'s background
+    pub fn with_no_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+    /// Get the `Pane`'s shadow
+    pub fn shadow(&self) -> Option<Shadow<R::Scalar>> {
+        self.shadow
+    }
+    /// Set the `Pane`'s shadow
+    pub fn with_shadow(mut self, shadow: Shadow<R::Scalar>) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    /// Remove the `Pane`'s shadow
+    pub fn with_no_shadow(mut self) -> Self {
+        self.shadow = None;
+        self
+    }
+    /// Get the `Pane`'s overflow policy
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+    /// Set the `Pane`'s overflow policy, which determines how its
+    /// contents and children are drawn if they do not fit inside it
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    /// Get the `Pane`'s text style
+    pub fn text_style(&self) -> TextStyle<R::Scalar> {
+        self.text_style
+    }
+    /// Set the `Pane`'s text style
+    ///
+    /// `Contents::StyledText` in this `Pane` or any of its descendants
+    /// inherits any fields of the style that it does not set itself.
+    pub fn with_text_style(mut self, text_style: TextStyle<R::Scalar>) -> Self {
+        self.text_style = text_style;
+        self
+    }
+    /// Get the `Pane`'s style classes
+    pub fn classes(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.classes.iter().map(String::as_str)
+    }
+    /// Check if the `Pane` has a style class
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+    /// Add a style class to the `Pane`
+    pub fn with_class<T>(mut self, class: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let class = class.into();
+        if !self.has_class(&class) {
+            self.classes.push(class);
+        }
+        self
+    }
+    /// Apply a `Theme` to every `Pane` in the `Pane`'s tree
+    ///
+    /// Each `Pane` is restyled using its classes and the name its parent
+    /// gives it. Properties that the theme does not set are left unchanged,
+    /// so themes meant to replace each other should set the same properties.
+    pub fn with_theme(mut self, theme: &Theme<R::Scalar>) -> Self {
+        self.apply_theme(theme, None);
+        self.update_rects();
+        self
+    }
+    fn apply_theme(&mut self, theme: &Theme<R::Scalar>, name: Option<&str>) {
+        let style = theme.resolve(self.classes(), name);
+        if let Some(color) = style.color {
+            self.color = color;
+        }
+        if style.background.is_some() {
+            self.background = style.background;
+        }
+        if style.border.is_some() {
+            self.border = style.border;
+        }
+        if let Some(margin) = style.margin {
+            self.margin = margin;
+        }
+        if let Some(text_style) = style.text_style {
+            self.text_style = text_style;
+        }
+        for (i, (_, pane)) in self.children.iter_mut().enumerate() {
+            let name = self
+                .names
+                .iter()
+                .find(|(_, &index)| index == i)
+                .map(|(name, _)| name.as_str());
+            pane.apply_theme(theme, name);
+        }
+        for popup in &mut self.popups {
+            popup.pane.apply_theme(theme, None);
+        }
+    }
+    /// Get the `Pane`'s layer, if it has one
+    pub fn layer(&self) -> Option<i32> {
+        self.layer
+    }
+    /// Set the `Pane`'s layer
+    ///
+    /// `Pane`s in higher layers are drawn above and hit-tested before those in
+    /// lower layers, regardless of where they are in the tree. `Pane`s without
+    /// a layer are in the same layer as their parent. The root is in layer `0`.
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = Some(layer);
+        self
+    }
+    /// Make the `Pane` use its parent's layer
+    pub fn with_no_layer(mut self) -> Self {
+        self.layer = None;
+        self
+    }
+    /// Get a descendant of the `Pane` by a path of child indices
+    ///
+    /// An empty path refers to the `Pane` itself.
+    pub fn descendant(&self, path: &[usize]) -> Option<&Pane<R>> {
+        path.iter().try_fold(self, |pane, &i| {
+            pane.children.get(i).map(|(_, child)| child)
+        })
+    }
+    /// Mutably get a descendant of the `Pane` by a path of child indices
+    ///
+    /// An empty path refers to the `Pane` itself.
+    pub fn descendant_mut(&mut self, path: &[usize]) -> Option<&mut Pane<R>> {
+        path.iter().try_fold(self, |pane, &i| {
+            pane.children.get_mut(i).map(|(_, child)| child)
+        })
+    }
+    /// Get the index of the `Pane`'s active tab, if its children are tabs
+    pub fn active_tab(&self) -> Option<usize> {
+        self.active_tab
+    }
+    /// Make the `Pane`'s children tabs and set which one is active
+    ///
+    /// Only the active tab is laid out and drawn. It fills the whole `Pane`.
+    pub fn with_active_tab(mut self, index: usize) -> Self {
+        self.active_tab = Some(index);
+        self.update_rects();
+        self
+    }
+    /// Make the `Pane`'s children no longer tabs
+    pub fn with_no_active_tab(mut self) -> Self {
+        self.active_tab = None;
+        self.update_rects();
+        self
+    }
+    /// Find where a `Pane` would be docked if it were dropped at some point
+    ///
+    /// The target is the topmost `Pane` in the tree at the point, ignoring popups.
+    /// If that `Pane` is a tab, its tab group is targeted instead.
+    pub fn dock_target<V>(&self, pos: V) -> Option<DockTarget<R>>
+    where
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        let pos = pos.map::<R::Vector>();
+        let mut path = self.tree_hit_test(pos)?;
+        if let Some((_, parent_path)) = path.split_last() {
+            if self.descendant(parent_path)?.active_tab.is_some() {
+                path.pop();
+            }
+        }
+        let rect = self.descendant(&path)?.rect;
+        let zone = DropZone::at(rect, pos);
+        Some(DockTarget {
+            path,
+            zone,
+            rect: zone.preview(rect),
+        })
+    }
+    /// Dock a `Pane` at a target
+    ///
+    /// Docking on an edge splits the target. If the target's parent is already
+    /// split in the same orientation, the docked `Pane` becomes the target's
+    /// sibling and takes half of its weight. Otherwise, the target is replaced
+    /// by a new `Pane` containing both. Docking in the center adds the docked
+    /// `Pane` as a tab, turning the target into a tab group if it is not one.
+    /// Names, breakpoints and popup anchor paths are updated to the new indices.
+    ///
+    /// If the target cannot be found, the `Pane` is left unchanged.
+    pub fn with_docked(mut self, target: &DockTarget<R>, pane: Pane<R>) -> Self {
+        self.dock(&target.path, target.zone, pane);
+        self.update_rects();
+        self
+    }
+    fn dock(&mut self, path: &[usize], zone: DropZone, pane: Pane<R>) {
+        let orientation = match (self.descendant(path), zone.orientation()) {
+            (None, _) => return,
+            (Some(_), Some(orientation)) => orientation,
+            (Some(target), None) => {
+                if target.active_tab.is_none() {
+                    self.wrap(path, Orientation::default());
+                }
+                let target = self.descendant_mut(path).expect("docking target");
+                target.children.push((R::Scalar::ONE, pane));
+                target.active_tab = Some(target.children.len() - 1);
+                return;
+            }
+        };
+        if let Some((&index, parent_path)) = path.split_last() {
+            let parent = self
+                .descendant_mut(parent_path)
+                .expect("parent of docking target");
+            if parent.active_tab.is_none() && parent.layout_orientation() == orientation {
+                let weight = parent.children[index].0 / R::Scalar::TWO;
+                parent.children[index].0 = weight;
+                // Reversed children are drawn in the opposite order of their indices
+                let before = zone.is_before() != (parent.direction == Direction::Reverse);
+                let insert = if before { index } else { index + 1 };
+                self.insert_descendant(parent_path, insert, weight, pane);
+                return;
+            }
+        }
+        self.wrap(path, orientation);
+        let insert = if zone.is_before() { 0 } else { 1 };
+        self.insert_descendant(path, insert, R::Scalar::ONE, pane);
+    }
+    /// Replace a `Pane` in the tree with a new `Pane` containing it as its only child
+    ///
+    /// The new `Pane` keeps the old one's rectangle and popups,
+    /// and the old one keeps its name.
+    fn wrap(&mut self, path: &[usize], orientation: Orientation) {
+        if self.descendant(path).is_none() {
+            return;
+        }
+        self.remap_anchors(&mut Vec::new(), &|p| Some(path_after_wrap(p, path)));
+        let name = path.split_last().and_then(|(&index, parent_path)| {
+            let parent = self.descendant_mut(parent_path)?;
+            let name = parent
+                .names
+                .iter()
+                .find(|(_, &i)| i == index)
+                .map(|(name, _)| name.clone())?;
+            parent.names.remove(&name);
+            Some(name)
+        });
+        let target = self.descendant_mut(path).expect("wrapped pane");
+        let mut inner = std::mem::take(target);
+        target.rect = inner.rect;
+        target.orientation = orientation;
+        target.wrapper = true;
+        target.popups = std::mem::take(&mut inner.popups);
+        for popup in &mut target.popups {
+            if let Anchor::Path(ref mut anchor) = popup.anchor {
+                anchor.insert(0, 0);
+            }
+        }
+        target.children.push((R::Scalar::ONE, inner));
+        if let Some(name) = name {
+            target.names.insert(name, 0);
+        }
+    }
+    /// Replace a wrapper `Pane` created by docking with its child if it has only
+    /// one, or remove it if it has none
+    fn collapse(&mut self, path: &[usize]) {
+        let (&index, parent_path) = match path.split_last() {
+            Some(split) => split,
+            None => return,
+        };
+        match self.descendant(path) {
+            Some(wrapper) if wrapper.wrapper && wrapper.children.is_empty() => {
+                self.remove_descendant(path);
+            }
+            Some(wrapper) if wrapper.wrapper && wrapper.children.len() == 1 => {
+                self.remap_anchors(&mut Vec::new(), &|p| Some(path_after_unwrap(p, path)));
+                let wrapper = self.descendant_mut(path).expect("collapsed pane");
+                let (_, mut child) = wrapper.children.pop().expect("collapsed pane's child");
+                let name = wrapper.names.drain().next().map(|(name, _)| name);
+                child.popups.append(&mut wrapper.popups);
+                *wrapper = child;
+                if let Some(name) = name {
+                    let parent = self
+                        .descendant_mut(parent_path)
+                        .expect("parent of collapsed pane");
+                    parent.names.insert(name, index);
+                }
+            }
+            _ => {}
+        }
+    }
+    /// Insert a child into a descendant, shifting the indices of later children
+    fn insert_descendant(
+        &mut self,
+        path: &[usize],
+        index: usize,
+        weight: R::Scalar,
+        pane: Pane<R>,
+    ) {
+        self.remap_anchors(&mut Vec::new(), &|p| {
+            Some(path_after_insert(p, path, index))
+        });
+        let parent = self.descendant_mut(path).expect("parent of inserted pane");
+        for i in parent.names.values_mut() {
+            if *i >= index {
+                *i += 1;
+            }
+        }
+        if let Some(tab) = parent.active_tab.as_mut() {
+            if *tab >= index {
+                *tab += 1;
+            }
+        }
+        for breakpoint in &mut parent.breakpoints {
+            for i in &mut breakpoint.hidden {
+                if *i >= index {
+                    *i += 1;
+                }
+            }
+        }
+        parent.hovered_splitter = None;
+        parent.children.insert(index, (weight, pane));
+    }
+    /// Remove a `Pane` from the tree by a path of child indices
+    ///
+    /// Returns the updated tree along with the removed `Pane`, if it was found.
+    /// If the `Pane` was the active tab, the next tab becomes active. Names,
+    /// breakpoints and popup anchor paths are updated to the new indices, and
+    /// popups anchored to the removed `Pane` by path are removed. Panes created
+    /// by docking which are left with one child are replaced by it, and ones
+    /// left empty are removed. The root cannot be undocked.
+    pub fn with_undocked(mut self, path: &[usize]) -> (Self, Option<Pane<R>>) {
+        let pane = self.remove_descendant(path);
+        self.update_rects();
+        (self, pane)
+    }
+    fn remove_descendant(&mut self, path: &[usize]) -> Option<Pane<R>> {
+        let (&index, parent_path) = path.split_last()?;
+        if index >= self.descendant(parent_path)?.children.len() {
+            return None;
+        }
+        self.remap_anchors(&mut Vec::new(), &|p| {
+            path_after_remove(p, parent_path, index)
+        });
+        let parent = self
+            .descendant_mut(parent_path)
+            .expect("parent of undocked pane");
+        let (_, pane) = parent.children.remove(index);
+        parent.names.retain(|_, i| *i != index);
+        for i in parent.names.values_mut() {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        if let Some(tab) = parent.active_tab {
+            parent.active_tab = if parent.children.is_empty() {
+                None
+            } else if tab > index || tab == parent.children.len() {
+                Some(tab - 1)
+            } else {
+                Some(tab)
+            };
+        }
+        for breakpoint in &mut parent.breakpoints {
+            breakpoint.hidden.retain(|&i| i != index);
+            for i in &mut breakpoint.hidden {
+                if *i > index {
+                    *i -= 1;
+                }
+            }
+        }
+        parent.hovered_splitter = None;
+        self.collapse(parent_path);
+        Some(pane)
+    }
+    /// Update the paths of popup anchors in the tree before it changes shape
+    ///
+    /// `remap` maps old paths from the root to new ones, or to `None` for
+    /// removed `Pane`s. Popups anchored to removed `Pane`s are removed.
+    fn remap_anchors<F>(&mut self, path: &mut Vec<usize>, remap: &F)
+    where
+        F: Fn(&[usize]) -> Option<Vec<usize>>,
+    {
+        if let Some(owner) = remap(path) {
+            self.popups.retain_mut(|popup| match popup.anchor {
+                Anchor::Path(ref mut anchor) => {
+                    let old: Vec<usize> = path.iter().chain(anchor.iter()).cloned().collect();
+                    match remap(&old) {
+                        Some(new) if new.starts_with(&owner) => {
+                            *anchor = new[owner.len()..].to_vec();
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+                Anchor::Name(_) => true,
+            });
+        }
+        for (i, (_, child)) in self.children.iter_mut().enumerate() {
+            path.push(i);
+            child.remap_anchors(path, remap);
+            path.pop();
+        }
+    }
+    /// Get the `Pane`'s popups
+    pub fn popups(&self) -> &[Popup<R>] {
+        &self.popups
+    }
+    /// Add a popup to the `Pane`
+    ///
+    /// The popup's anchor is searched for in this `Pane`'s tree, and the popup
+    /// is kept inside this `Pane`'s rectangle, so popups are usually added to
+    /// the root. The popups of all visible `Pane`s in a tree are drawn after
+    /// the tree, in tree order, and are hit-tested before it.
+    pub fn with_popup(mut self, popup: Popup<R>) -> Self {
+        self.popups.push(popup);
+        self.update_rects();
+        self
+    }
+    /// Remove all of the `Pane`'s popups
+    pub fn with_no_popups(mut self) -> Self {
+        self.popups.clear();
+        self
+    }
+    /// Find the topmost `Pane` at some point
+    ///
+    /// Popups are tested first, latest first. Then `Pane`s in the tree are tested
+    /// in the reverse of the order they are drawn in, so the result is the `Pane`
+    /// that appears on top. Hidden `Pane`s are skipped, and points outside a
+    /// clipping `Pane` do not hit its children.
+    pub fn hit_test<V>(&self, pos: V) -> Option<Hit>
+    where
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        let pos = pos.map::<R::Vector>();
+        let mut popups = Vec::new();
+        self.collect_popups(&mut Vec::new(), TextStyle::new(), &mut popups);
+        popups
+            .into_iter()
+            .rev()
+            .find_map(|item| {
+                item.popup
+                    .pane
+                    .hit_test(pos)
+                    .map(|hit| Hit::Popup(item.owner, item.index, Box::new(hit)))
+            })
+            .or_else(|| self.tree_hit_test(pos).map(Hit::Tree))
+    }
+    /// Get the `Pane` referred to by a hit
+    pub fn hit_pane(&self, hit: &Hit) -> Option<&Pane<R>> {
+        match hit {
+            Hit::Tree(path) => self.descendant(path),
+            Hit::Popup(owner, i, hit) => self.descendant(owner)?.popups.get(*i)?.pane.hit_pane(hit),
+        }
+    }
+    /// Collect the placed popups of the visible `Pane`s in the tree
+    /// in the order they are drawn in
+    fn collect_popups<'a>(
+        &'a self,
+        path: &mut Vec<usize>,
+        inherited: TextStyle<R::Scalar>,
+        popups: &mut Vec<PopupItem<'a, R>>,
+    ) {
+        let style = self.text_style.inherit(inherited);
+        for (index, popup) in self.popups.iter().enumerate() {
+            if popup.placed_side.is_some() {
+                popups.push(PopupItem {
+                    owner: path.clone(),
+                    index,
+                    style,
+                    popup,
+                });
+            }
+        }
+        for (i, (_, pane)) in self.children.iter().enumerate() {
+            if !pane.hidden {
+                path.push(i);
+                pane.collect_popups(path, style, popups);
+                path.pop();
+            }
+        }
+    }
+    /// Get the topmost `Pane` at some point
+    pub fn pane_at<V>(&self, pos: V) -> Option<&Pane<R>>
+    where
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        self.hit_test(pos).and_then(|hit| self.hit_pane(&hit))
+    }
+    /// Get the path of child indices to the topmost `Pane` in the tree at some point,
+    /// ignoring popups
+    fn tree_hit_test(&self, pos: R::Vector) -> Option<Vec<usize>> {
+        let mut hits = Vec::new();
+        self.collect_hits(pos, &mut Vec::new(), 0, &mut hits);
+        hits.sort_by_key(|(layer, _)| *layer);
+        hits.pop().map(|(_, path)| path)
+    }
+    fn collect_hits(
+        &self,
+        pos: R::Vector,
+        path: &mut Vec<usize>,
+        layer: i32,
+        hits: &mut Vec<(i32, Vec<usize>)>,
+    ) {
+        let layer = self.layer.unwrap_or(layer);
+        let inside = self.rect.contains(pos);
+        if inside {
+            hits.push((layer, path.clone()));
+        }
+        if !inside && self.overflow != Overflow::Visible {
+            return;
+        }
+        for (i, (_, pane)) in self.children.iter().enumerate() {
+            let fits = self.overflow != Overflow::Hidden || self.rect.contains_rect(pane.rect);
+            if !pane.hidden && fits {
+                path.push(i);
+                pane.collect_hits(pos, path, layer, hits);
+                path.pop();
+            }
+        }
+    }
+    /// Get the `Pane`'s splitter style
+    pub fn splitter(&self) -> Option<Splitter<R::Scalar>> {
+        self.splitter
+    }
+    /// Set the `Pane`'s splitter style
+    ///
+    /// Splitters are drawn in the gutters between the `Pane`'s children.
+    /// The gutters are as wide as the `Pane`'s margin.
+    pub fn with_splitter(mut self, splitter: Splitter<R::Scalar>) -> Self {
+        self.splitter = Some(splitter);
+        self
+    }
+    /// Remove the `Pane`'s splitters
+    pub fn with_no_splitter(mut self) -> Self {
+        self.splitter = None;
+        self
+    }
+    /// Get the rectangles of the `Pane`'s splitters
+    ///
+    /// There is one splitter between each pair of adjacent visible children.
+    /// Splitters are centered in their gutters. If the `Pane` has no splitter
+    /// style, this is empty.
+    pub fn splitter_rects(&self) -> Vec<R> {
+        let splitter = match self.splitter {
+            Some(splitter) => splitter,
+            None => return Vec::new(),
+        };
+        let margin_rect = self.margin_rect();
+        let orientation = self.layout_orientation();
+        let visible: Vec<R> = self
+            .children
+            .iter()
+            .filter(|(_, pane)| !pane.hidden)
+            .map(|(_, pane)| pane.rect)
+            .collect();
+        visible
+            .windows(2)
+            .map(|pair| {
+                let (a, b) = (pair[0], pair[1]);
+                match orientation {
+                    Orientation::Horizontal => {
+                        let (start, end) = if a.left() < b.left() {
+                            (a.right(), b.left())
+                        } else {
+                            (b.right(), a.left())
+                        };
+                        let center = (start + end) / R::Scalar::TWO;
+                        R::new(
+                            R::Vector::new(
+                                center - splitter.thickness / R::Scalar::TWO,
+                                margin_rect.top(),
+                            ),
+                            R::Vector::new(splitter.thickness, margin_rect.height()),
+                        )
+                    }
+                    Orientation::Vertical => {
+                        let (start, end) = if a.top() < b.top() {
+                            (a.bottom(), b.top())
+                        } else {
+                            (b.bottom(), a.top())
+                        };
+                        let center = (start + end) / R::Scalar::TWO;
+                        R::new(
+                            R::Vector::new(
+                                margin_rect.left(),
+                                center - splitter.thickness / R::Scalar::TWO,
+                            ),
+                            R::Vector::new(margin_rect.width(), splitter.thickness),
+                        )
+                    }
+                }
+            })
+            .collect()
+    }
+    /// Get the index of the `Pane`'s splitter at some point
+    ///
+    /// This tests against the same rectangles that the splitters are drawn in.
+    /// Only this `Pane`'s splitters are tested, not those of its children.
+    pub fn splitter_at<V>(&self, pos: V) -> Option<usize>
+    where
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        self.splitter_rects()
+            .into_iter()
+            .position(|rect| rect.contains(pos.map::<R::Vector>()))
+    }
+    /// Get the index of the `Pane`'s hovered splitter
+    pub fn hovered_splitter(&self) -> Option<usize> {
+        self.hovered_splitter
+    }
+    /// Update which splitters are hovered over in the `Pane`'s
+    /// tree given a cursor position
+    pub fn with_hover<V>(mut self, pos: V) -> Self
+    where
+        V: Vector2<Scalar = R::Scalar>,
+    {
+        self.update_hover(pos.map::<R::Vector>());
+        self
+    }
+    fn update_hover(&mut self, pos: R::Vector) {
+        self.hovered_splitter = self.splitter_at(pos);
+        for (_, pane) in &mut self.children {
+            pane.update_hover(pos);
+        }
+    }
     /// Get the `Pane`'s margin
     pub fn margin(&self) -> R::Scalar {
         self.margin
@@ -279,45 +1145,235 @@ where
         self.update_rects();
         self
     }
-    /// Get the inner rectangle created by the `Pane` and its margin
+    /// Get the pixel size that inner `Pane`s' rectangles are snapped to, if any
+    pub fn pixel_snap(&self) -> Option<R::Scalar> {
+        self.pixel_snap
+    }
+    /// Snap the edges of all inner `Pane`s' rectangles to multiples of the given pixel size
+    ///
+    /// Use `1.0` to snap to integer boundaries or the reciprocal of a display's scale
+    /// factor to snap to device pixels. Because edges are snapped rather than sizes,
+    /// the total size of the children is kept exact and adjacent `Pane`s still touch.
+    /// Inner `Pane`s inherit this setting.
+    ///
+    /// A pixel size which is not positive, such as zero or NaN,
+    /// stops snapping like `Pane::with_no_pixel_snap`.
+    pub fn with_pixel_snap(mut self, pixel_size: R::Scalar) -> Self {
+        if pixel_size.partial_cmp(&R::Scalar::ZERO) != Some(Ordering::Greater) {
+            return self.with_no_pixel_snap();
+        }
+        self.pixel_snap = Some(pixel_size);
+        self.update_rects();
+        self
+    }
+    /// Stop snapping the rectangles of the `Pane` and all its inner `Pane`s
+    pub fn with_no_pixel_snap(mut self) -> Self {
+        self.clear_pixel_snap();
+        self.update_rects();
+        self
+    }
+    fn clear_pixel_snap(&mut self) {
+        self.pixel_snap = None;
+        for (_, pane) in &mut self.children {
+            pane.clear_pixel_snap();
+        }
+    }
+    /// Get the `Pane`'s border
+    pub fn border(&self) -> Option<Border<R::Scalar>> {
+        self.border
+    }
+    /// Set the `Pane`'s border
+    pub fn with_border(mut self, border: Border<R::Scalar>) -> Self {
+        self.border = Some(border);
+        self.update_rects();
+        self
+    }
+    /// Remove the `Pane`'s border
+    pub fn with_no_border(mut self) -> Self {
+        self.border = None;
+        self.update_rects();
+        self
+    }
+    /// Get the radius of the `Pane`'s rounded corners
+    pub fn corner_radius(&self) -> R::Scalar {
+        self.corner_radius
+    }
+    /// Set the radius of the `Pane`'s rounded corners
+    pub fn with_corner_radius(mut self, corner_radius: R::Scalar) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+    /// Get the inner rectangle created by the `Pane`, its margin, and its border
     pub fn margin_rect(&self) -> R {
+        let margin = R::Vector::new(self.margin, self.margin);
+        let (top_left, bottom_right) = match self.border {
+            Some(border) => border.insets::<R::Vector>(),
+            None => (
+                R::Vector::new(R::Scalar::ZERO, R::Scalar::ZERO),
+                R::Vector::new(R::Scalar::ZERO, R::Scalar::ZERO),
+            ),
+        };
         R::new(
-            self.rect
-                .top_left()
-                .add(R::Vector::new(self.margin, self.margin)),
+            self.rect.top_left().add(margin).add(top_left),
             self.rect
                 .size()
-                .sub(R::Vector::new(self.margin, self.margin).mul(R::Scalar::TWO)),
+                .sub(margin.mul(R::Scalar::TWO))
+                .sub(top_left)
+                .sub(bottom_right),
         )
     }
     /// Update the size of all inner `Pane`s' rectangles
     fn update_rects(&mut self) {
         let margin_rect = self.margin_rect();
-        let new_rects = self.orientation.split_rect(
-            self.margin,
-            margin_rect,
-            self.children.iter().map(|(w, _)| *w),
-        );
-        for (pane, rect) in self.children.iter_mut().zip(new_rects) {
-            pane.1.rect = rect;
+        let hidden: Vec<usize> = self
+            .breakpoints
+            .iter()
+            .filter(|bp| bp.condition.is_met(self.rect))
+            .flat_map(|bp| bp.hidden.iter().cloned())
+            .collect();
+        for (i, (_, pane)) in self.children.iter_mut().enumerate() {
+            pane.hidden = hidden.contains(&i) || self.active_tab.is_some_and(|tab| tab != i);
+        }
+        let mut new_rects = self
+            .layout_orientation()
+            .split_rect(
+                self.direction,
+                self.margin,
+                margin_rect,
+                self.children
+                    .iter()
+                    .filter(|(_, pane)| !pane.hidden)
+                    .map(|(w, _)| *w),
+            )
+            .into_iter();
+        let weighted: Vec<Option<R>> = self
+            .children
+            .iter()
+            .map(|(_, pane)| {
+                if pane.hidden {
+                    None
+                } else {
+                    Some(new_rects.next().unwrap_or(margin_rect))
+                }
+            })
+            .collect();
+        // Siblings are solved together, so if the weighted rectangle that any of
+        // them was solved from changes, all of their solved rectangles are dropped
+        let stale = self
+            .children
+            .iter()
+            .zip(&weighted)
+            .any(|((_, pane), rect)| match (pane.solved_from, rect) {
+                (Some(from), Some(rect)) => !same_rect(from, *rect),
+                (Some(_), None) => true,
+                (None, _) => false,
+            });
+        if stale {
+            for (_, pane) in &mut self.children {
+                pane.solved_rect = None;
+                pane.solved_from = None;
+            }
+        }
+        for (pane, rect) in self.children.iter_mut().zip(weighted) {
+            if self.pixel_snap.is_some() {
+                pane.1.pixel_snap = self.pixel_snap;
+            }
+            // Hidden panes are collapsed, and their trees are not laid out
+            // until they are shown again
+            let rect = match rect {
+                Some(rect) => rect,
+                None => {
+                    pane.1.rect = R::new(
+                        margin_rect.top_left(),
+                        R::Vector::new(R::Scalar::ZERO, R::Scalar::ZERO),
+                    );
+                    continue;
+                }
+            };
+            if pane.1.solved_rect.is_some() && pane.1.solved_from.is_none() {
+                pane.1.solved_from = Some(rect);
+            }
+            let rect = pane.1.solved_rect.unwrap_or(rect);
+            pane.1.rect = match self.pixel_snap {
+                Some(pixel_size) => rect.snapped(pixel_size),
+                None => rect,
+            };
             pane.1.update_rects();
         }
+        let anchors: Vec<Option<R>> = self
+            .popups
+            .iter()
+            .map(|popup| {
+                match popup.anchor {
+                    Anchor::Name(ref name) => self.find(name),
+                    Anchor::Path(ref path) => self.descendant(path),
+                }
+                .map(|pane| pane.rect)
+            })
+            .collect();
+        let bounds = self.rect;
+        for (popup, anchor) in self.popups.iter_mut().zip(anchors) {
+            popup.place(anchor, bounds);
+        }
+    }
+    /// Take a snapshot of the rectangles and colors of the `Pane`'s tree
+    pub fn layout(&self) -> Layout<R> {
+        Layout {
+            rect: self.rect,
+            color: self.color,
+            children: self.children().map(Pane::layout).collect(),
+        }
+    }
+    /// Apply a snapshot of rectangles and colors to the `Pane`'s tree
+    ///
+    /// Unlike other methods, this does not recalculate the rectangles of
+    /// inner `Pane`s, so it can be used to draw the in-between states of a `Tween`.
+    /// Children without a corresponding layout are left unchanged.
+    pub fn with_layout(mut self, layout: &Layout<R>) -> Self {
+        self.apply_layout(layout);
+        self
+    }
+    fn apply_layout(&mut self, layout: &Layout<R>) {
+        self.rect = layout.rect;
+        self.color = layout.color;
+        for ((_, pane), layout) in self.children.iter_mut().zip(&layout.children) {
+            pane.apply_layout(layout);
+        }
     }
-    /// Recursively fit the text of any `Contents::Text` in the `Pane`'s tree
-    pub fn fit_text<C>(mut self, glyphs: &mut C) -> Self
+    /// Recursively fit the text of any `Contents` in the `Pane`'s tree
+    ///
+    /// The fitted font sizes are kept separately from the contents and text styles,
+    /// so fitting again after resizing starts from the original font sizes.
+    pub fn fit_text<C>(self, glyphs: &mut C) -> Self
+    where
+        C: CharacterWidthCache<Scalar = R::Scalar>,
+    {
+        self.fit_text_styled(glyphs, TextStyle::new())
+    }
+    fn fit_text_styled<C>(mut self, glyphs: &mut C, inherited: TextStyle<R::Scalar>) -> Self
     where
         C: CharacterWidthCache<Scalar = R::Scalar>,
     {
         self.update_rects();
-        let margin_rect = self.margin_rect();
-        if let Some(Contents::Text(ref text, ref mut format)) = self.contents {
-            *format = format.resize_font(glyphs.fit_max_font_size(text, margin_rect, *format));
+        if self.hidden {
+            return self;
         }
+        let margin_rect = self.margin_rect();
+        let style = self.text_style.inherit(inherited);
+        self.fitted_font_size = self.contents.as_ref().map(|contents| {
+            let format = contents.format(style);
+            let max_size = glyphs.fit_max_font_size(contents.text_str(), margin_rect, format);
+            format.resize_font(max_size).font_size
+        });
         self.children = self
             .children
             .into_iter()
-            .map(|(w, pane)| (w, pane.fit_text(glyphs)))
+            .map(|(w, pane)| (w, pane.fit_text_styled(glyphs, style)))
             .collect();
+        for popup in &mut self.popups {
+            let pane = std::mem::take(&mut popup.pane);
+            popup.pane = pane.fit_text_styled(glyphs, style);
+        }
         self
     }
 }
@@ -326,6 +1382,102 @@ impl<R> Pane<R>
 where
     R: Rectangle<Scalar = f64>,
 {
+    /// Solve the layout constraints of all `Pane`s in the tree
+    ///
+    /// Named `Pane`s which are referred to by a constraint are moved
+    /// and resized to satisfy it, while otherwise keeping as close as
+    /// possible to the sizes defined by their weights. Their visible siblings
+    /// are solved along with them, so siblings stay side by side and inside
+    /// their parent. Constraints which cannot be satisfied are skipped and
+    /// can be checked with `Pane::skipped_constraints`.
+    ///
+    /// The solved rectangles are dropped when the layout they were solved from
+    /// changes, such as when the `Pane` is resized or a margin, orientation,
+    /// weight or breakpoint changes, so call this again after such changes.
+    #[cfg(feature = "constraints")]
+    pub fn solve_constraints(mut self) -> Self {
+        self.clear_solved_rects();
+        self.update_rects();
+        let constraints = self.all_constraints();
+        // Find the constrained panes and the groups of siblings they are in
+        let mut paths: Vec<Vec<usize>> = Vec::new();
+        let mut parent_paths: Vec<Vec<usize>> = Vec::new();
+        let mut names = HashMap::new();
+        let constrained = constraints.iter().flat_map(|c| {
+            Some(&c.pane)
+                .into_iter()
+                .chain(c.target.as_ref().map(|t| &t.0))
+        });
+        for name in constrained {
+            let path = match self.find_path(name) {
+                Some(path) if !names.contains_key(name) => path,
+                _ => continue,
+            };
+            let parent_path = &path[..path.len() - 1];
+            if !parent_paths.iter().any(|p| p == parent_path) {
+                let parent = self
+                    .descendant(parent_path)
+                    .expect("constrained pane's parent");
+                for (i, (_, child)) in parent.children.iter().enumerate() {
+                    if !child.hidden {
+                        paths.push(parent_path.iter().cloned().chain(Some(i)).collect());
+                    }
+                }
+                parent_paths.push(parent_path.to_vec());
+            }
+            if let Some(index) = paths.iter().position(|p| *p == path) {
+                names.insert(name.clone(), index);
+            }
+        }
+        let groups: Vec<SiblingGroup<R>> = parent_paths
+            .iter()
+            .map(|parent_path| {
+                let parent = self
+                    .descendant(parent_path)
+                    .expect("constrained pane's parent");
+                let mut children: Vec<usize> = paths
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, path)| path[..path.len() - 1] == parent_path[..])
+                    .map(|(i, _)| i)
+                    .collect();
+                if parent.direction == Direction::Reverse {
+                    children.reverse();
+                }
+                SiblingGroup {
+                    parent: paths.iter().position(|p| p == parent_path),
+                    parent_rect: parent.rect,
+                    horizontal: parent.layout_orientation() == Orientation::Horizontal,
+                    children,
+                }
+            })
+            .collect();
+        let rects: Vec<R> = paths
+            .iter()
+            .map(|path| self.descendant(path).expect("constrained pane").rect)
+            .collect();
+        let (solved, skipped) = constraint::solve(&constraints, &rects, &names, &groups);
+        for (path, rect) in paths.iter().zip(solved) {
+            if let Some(pane) = self.descendant_mut(path) {
+                pane.solved_rect = Some(rect);
+            }
+        }
+        self.skipped_constraints = skipped;
+        self.update_rects();
+        self
+    }
+    /// Get the path of child indices to a `Pane` in the tree, searching as `Pane::find` does
+    #[cfg(feature = "constraints")]
+    fn find_path(&self, name: &str) -> Option<Vec<usize>> {
+        if let Some(&index) = self.names.get(name) {
+            return Some(vec![index]);
+        }
+        self.children.iter().enumerate().find_map(|(i, (_, pane))| {
+            let mut path = pane.find_path(name)?;
+            path.insert(0, i);
+            Some(path)
+        })
+    }
     /// Draw the `Pane` and all its contents to something using
     /// the `piston2d-graphics` crate
     #[cfg(feature = "graphics")]
@@ -340,29 +1492,279 @@ where
         C: CharacterCache<Texture = T>,
         G: Graphics<Texture = T>,
     {
-        rectangle(
+        self.draw_with_state(glyphs, &DrawState::default(), transform, graphics)
+    }
+    /// Draw the `Pane` and all its contents to something using
+    /// the `piston2d-graphics` crate with the given draw state
+    ///
+    /// `Pane`s with `Overflow::Clip` combine their own scissor rectangles
+    /// with any scissor in the draw state. Without a viewport, scissor rectangles
+    /// are taken from the `Pane`s' rectangles, so clipping assumes that one unit
+    /// in a `Pane` is one pixel of the render target. Use `Pane::draw_with_textures`
+    /// with a context that has a viewport to clip correctly when drawing
+    /// transformed or on HiDPI displays.
+    ///
+    /// `Pane`s are drawn in order of their layers. `Pane`s in the same layer
+    /// are drawn in tree order. Popups are drawn last.
+    ///
+    /// Image backgrounds and radial gradients are drawn with textures, so they
+    /// are skipped. Use `Pane::draw_with_textures` to draw them.
+    #[cfg(feature = "graphics")]
+    pub fn draw_with_state<T, C, G>(
+        &self,
+        glyphs: &mut C,
+        draw_state: &DrawState,
+        transform: Matrix2d,
+        graphics: &mut G,
+    ) -> Result<(), C::Error>
+    where
+        T: ImageSize,
+        C: CharacterCache<Texture = T>,
+        G: Graphics<Texture = T>,
+    {
+        let context = Context {
+            viewport: None,
+            view: transform,
+            transform,
+            draw_state: *draw_state,
+        };
+        self.draw_with_textures(glyphs, &mut NoTextures::new(), &context, graphics)
+    }
+    /// Draw the `Pane` and all its contents to something using
+    /// the `piston2d-graphics` crate in a context, getting the
+    /// textures for backgrounds from a `TextureSource`
+    ///
+    /// If the context has a viewport, scissor rectangles are mapped through the
+    /// context's transform and the viewport into pixels of the render target.
+    /// This is otherwise the same as `Pane::draw_with_state`.
+    #[cfg(feature = "graphics")]
+    pub fn draw_with_textures<T, C, X, G>(
+        &self,
+        glyphs: &mut C,
+        textures: &mut X,
+        context: &Context,
+        graphics: &mut G,
+    ) -> Result<(), C::Error>
+    where
+        T: ImageSize,
+        C: CharacterCache<Texture = T>,
+        X: TextureSource<Texture = T>,
+        G: Graphics<Texture = T>,
+    {
+        self.draw_styled(glyphs, textures, context, TextStyle::new(), graphics)
+    }
+    #[cfg(feature = "graphics")]
+    fn draw_styled<T, C, X, G>(
+        &self,
+        glyphs: &mut C,
+        textures: &mut X,
+        context: &Context,
+        inherited: TextStyle<f64>,
+        graphics: &mut G,
+    ) -> Result<(), C::Error>
+    where
+        T: ImageSize,
+        C: CharacterCache<Texture = T>,
+        X: TextureSource<Texture = T>,
+        G: Graphics<Texture = T>,
+    {
+        let mut items = Vec::new();
+        self.collect_draw_items(*context, inherited, 0, &mut items);
+        items.sort_by_key(|item| item.layer);
+        for item in items {
+            let item_context = Context {
+                draw_state: item.draw_state,
+                ..*context
+            };
+            if item.splitters {
+                item.pane
+                    .draw_splitters(&item.draw_state, context.transform, graphics);
+            } else {
+                item.pane
+                    .draw_self(glyphs, textures, &item_context, item.style, graphics)?;
+            }
+        }
+        let mut popups = Vec::new();
+        self.collect_popups(&mut Vec::new(), inherited, &mut popups);
+        for item in popups {
+            item.popup
+                .pane
+                .draw_styled(glyphs, textures, context, item.style, graphics)?;
+        }
+        Ok(())
+    }
+    /// Flatten the `Pane`'s tree into the parts to draw, in tree order
+    #[cfg(feature = "graphics")]
+    fn collect_draw_items<'a>(
+        &'a self,
+        context: Context,
+        inherited: TextStyle<f64>,
+        layer: i32,
+        items: &mut Vec<DrawItem<'a, R>>,
+    ) {
+        let layer = self.layer.unwrap_or(layer);
+        let style = self.text_style.inherit(inherited);
+        items.push(DrawItem {
+            pane: self,
+            layer,
+            draw_state: context.draw_state,
+            style,
+            splitters: false,
+        });
+        let inner_context = Context {
+            draw_state: self.inner_draw_state(&context),
+            ..context
+        };
+        for (_, pane) in &self.children {
+            let fits = self.overflow != Overflow::Hidden || self.rect.contains_rect(pane.rect);
+            if !pane.hidden && fits {
+                pane.collect_draw_items(inner_context, style, layer, items);
+            }
+        }
+        if self.splitter.is_some() {
+            items.push(DrawItem {
+                pane: self,
+                layer,
+                draw_state: inner_context.draw_state,
+                style,
+                splitters: true,
+            });
+        }
+    }
+    /// Get the draw state for the `Pane`'s contents and children
+    #[cfg(feature = "graphics")]
+    fn inner_draw_state(&self, context: &Context) -> DrawState {
+        let draw_state = context.draw_state;
+        match self.overflow {
+            Overflow::Clip => draw_state.scissor(style::scissor(
+                self.rect,
+                context.transform,
+                context.viewport,
+                draw_state.scissor,
+            )),
+            Overflow::Visible | Overflow::Hidden => draw_state,
+        }
+    }
+    /// Draw the `Pane` and its contents, but not its children
+    #[cfg(feature = "graphics")]
+    fn draw_self<T, C, X, G>(
+        &self,
+        glyphs: &mut C,
+        textures: &mut X,
+        context: &Context,
+        style: TextStyle<f64>,
+        graphics: &mut G,
+    ) -> Result<(), C::Error>
+    where
+        T: ImageSize,
+        C: CharacterCache<Texture = T>,
+        X: TextureSource<Texture = T>,
+        G: Graphics<Texture = T>,
+    {
+        let draw_state = &context.draw_state;
+        let transform = context.transform;
+        let corner_radius = style::clamp_radius(self.rect, self.corner_radius);
+        if let Some(shadow) = self.shadow {
+            shadow.draw(self.rect, corner_radius, draw_state, transform, graphics);
+        }
+        style::fill_rect(
             self.color,
-            self.rect().map::<[f64; 4]>(),
+            self.rect,
+            corner_radius,
+            draw_state,
             transform,
             graphics,
         );
+        if let Some(ref background) = self.background {
+            background.draw(
+                self.rect,
+                corner_radius,
+                textures,
+                draw_state,
+                transform,
+                graphics,
+            );
+        }
+        if let Some(border) = self.border {
+            border.draw(self.rect, corner_radius, draw_state, transform, graphics);
+        }
         if let Some(ref contents) = self.contents {
-            match contents {
-                Contents::Text(text, format) => justified_text(
-                    text,
-                    self.margin_rect().map::<[f64; 4]>(),
-                    *format,
-                    glyphs,
-                    transform,
-                    graphics,
-                )?,
+            let mut format = contents.format(style);
+            if let Some(font_size) = self.fitted_font_size {
+                format.font_size = font_size;
             }
-        }
-        for (_, pane) in &self.children {
-            pane.draw(glyphs, transform, graphics)?;
+            let margin_rect = self.margin_rect();
+            let mut lines = glyphs.justify_words(contents.text_str(), margin_rect, format);
+            if self.overflow == Overflow::Hidden {
+                let LineMetrics {
+                    ascent, descent, ..
+                } = glyphs.line_metrics(format.font_size);
+                lines.retain(|words| {
+                    words.iter().all(|(pos, word)| {
+                        let size = [
+                            CharacterWidthCache::width(glyphs, word, format.font_size),
+                            ascent + descent,
+                        ];
+                        margin_rect.contains_rect([[pos.x(), pos.y() - ascent], size])
+                    })
+                });
+            }
+            let inner_state = self.inner_draw_state(context);
+            draw_words(
+                &lines,
+                contents.text_str(),
+                margin_rect,
+                format,
+                glyphs,
+                &inner_state,
+                transform,
+                context.viewport,
+                graphics,
+            )?;
         }
         Ok(())
     }
+    /// Draw the `Pane`'s splitters
+    #[cfg(feature = "graphics")]
+    fn draw_splitters<G>(&self, draw_state: &DrawState, transform: Matrix2d, graphics: &mut G)
+    where
+        G: Graphics,
+    {
+        if let Some(splitter) = self.splitter {
+            for (i, rect) in self.splitter_rects().into_iter().enumerate() {
+                let color = splitter.current_color(self.hovered_splitter == Some(i));
+                style::fill_rect(color, rect, 0.0, draw_state, transform, graphics);
+            }
+        }
+    }
+}
+
+/// A popup in a `Pane`'s tree
+struct PopupItem<'a, R>
+where
+    R: Rectangle,
+{
+    /// The path of child indices to the `Pane` which has the popup
+    owner: Vec<usize>,
+    /// The index of the popup in its `Pane`
+    index: usize,
+    /// The text style the popup inherits
+    #[cfg_attr(not(feature = "graphics"), allow(dead_code))]
+    style: TextStyle<R::Scalar>,
+    popup: &'a Popup<R>,
+}
+
+/// A part of a `Pane` to be drawn in layer order
+#[cfg(feature = "graphics")]
+struct DrawItem<'a, R>
+where
+    R: Rectangle,
+{
+    pane: &'a Pane<R>,
+    layer: i32,
+    draw_state: DrawState,
+    style: TextStyle<R::Scalar>,
+    splitters: bool,
 }
 
 impl<R> ops::Index<usize> for Pane<R>
@@ -386,6 +1788,60 @@ where
     }
 }
 
+/// Check if two rectangles have the same position and size
+fn same_rect<R>(a: R, b: R) -> bool
+where
+    R: Rectangle,
+{
+    a.left() == b.left() && a.top() == b.top() && a.width() == b.width() && a.height() == b.height()
+}
+
+/// Map a path in a tree to where it is after a child is inserted
+/// at an index of the `Pane` at `parent`
+fn path_after_insert(path: &[usize], parent: &[usize], index: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    if path.len() > parent.len() && path.starts_with(parent) && path[parent.len()] >= index {
+        path[parent.len()] += 1;
+    }
+    path
+}
+
+/// Map a path in a tree to where it is after a child is removed
+/// from an index of the `Pane` at `parent`
+///
+/// Returns `None` for paths to the removed child and its descendants.
+fn path_after_remove(path: &[usize], parent: &[usize], index: usize) -> Option<Vec<usize>> {
+    let mut path = path.to_vec();
+    if path.len() > parent.len() && path.starts_with(parent) {
+        let i = &mut path[parent.len()];
+        match (*i).cmp(&index) {
+            Ordering::Equal => return None,
+            Ordering::Greater => *i -= 1,
+            Ordering::Less => {}
+        }
+    }
+    Some(path)
+}
+
+/// Map a path in a tree to where it is after the `Pane` at `target` is wrapped in a new `Pane`
+fn path_after_wrap(path: &[usize], target: &[usize]) -> Vec<usize> {
+    let mut path = path.to_vec();
+    if path.starts_with(target) {
+        path.insert(target.len(), 0);
+    }
+    path
+}
+
+/// Map a path in a tree to where it is after the wrapper `Pane`
+/// at `target` is replaced by its only child
+fn path_after_unwrap(path: &[usize], target: &[usize]) -> Vec<usize> {
+    let mut path = path.to_vec();
+    if path.len() > target.len() && path.starts_with(target) {
+        path.remove(target.len());
+    }
+    path
+}
+
 trait Map<I> {
     type Accessed;
     fn map<F>(self, index: I, f: F) -> Self
@@ -511,6 +1967,205 @@ pub mod color {
     pub const WHITE: Color = [1.0; 4];
     /// Transparent
     pub const TRANSPARENT: Color = [0.0; 4];
+
+    /// Linearly interpolate between two colors
+    ///
+    /// A `t` of `0` gives the first color and a `t` of `1` gives the second.
+    pub fn lerp(from: Color, to: Color, t: f32) -> Color {
+        let mut color = from;
+        for (c, to) in color.iter_mut().zip(&to) {
+            *c += (to - *c) * t;
+        }
+        color
+    }
 }
 
 pub use self::color::Color;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_formats_override_inherited_styles() {
+        let inherited = TextStyle::new().font_size(30.0).centered();
+        let text = Contents::<f64>::text("x", 12u32);
+        assert_eq!(text.format(inherited).font_size, 12.0);
+        assert_eq!(text.format(inherited).just, Justification::Left);
+        let styled = Contents::<f64>::styled_text("x", TextStyle::new().font_size(12.0));
+        assert_eq!(styled.format(inherited).font_size, 12.0);
+        assert_eq!(styled.format(inherited).just, Justification::Centered);
+    }
+
+    #[test]
+    fn non_positive_pixel_snap_does_not_snap() {
+        let thirds = || {
+            Pane::new()
+                .with_rect([0.0, 0.0, 100.0, 10.0])
+                .with_panes(vec!["a", "b", "c"])
+        };
+        for &pixel_size in &[0.0, -1.0, f64::NAN] {
+            let root = thirds().with_pixel_snap(1.0).with_pixel_snap(pixel_size);
+            assert_eq!(root.pixel_snap(), None);
+            assert_eq!(root["b"].rect(), thirds()["b"].rect());
+            let rect = [0.5, 0.5, 1.0, 1.0];
+            assert_eq!(rect.snapped(pixel_size), rect);
+        }
+    }
+
+    fn split(direction: Direction) -> Pane {
+        Pane::new()
+            .with_rect([0.0, 0.0, 100.0, 10.0])
+            .with_orientation(Orientation::Horizontal)
+            .with_direction(direction)
+            .with_panes(vec!["a", "b"])
+    }
+
+    #[cfg(feature = "constraints")]
+    fn constrained() -> Pane {
+        split(Direction::Forward)
+            .with_constraint(Constraint::new("a", Edge::Width).plus(70.0))
+            .solve_constraints()
+    }
+
+    #[test]
+    #[cfg(feature = "constraints")]
+    fn constraints_keep_siblings_side_by_side() {
+        let root = constrained();
+        assert!(root.skipped_constraints().is_empty());
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 70.0, 10.0]);
+        assert_eq!(root["b"].rect(), [70.0, 0.0, 30.0, 10.0]);
+        let root = split(Direction::Reverse)
+            .with_constraint(Constraint::new("a", Edge::Width).plus(70.0))
+            .solve_constraints();
+        assert_eq!(root["a"].rect(), [30.0, 0.0, 70.0, 10.0]);
+        assert_eq!(root["b"].rect(), [0.0, 0.0, 30.0, 10.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "constraints")]
+    fn constraints_keep_children_inside_solved_parents() {
+        let root = Pane::new()
+            .with_rect([0.0, 0.0, 100.0, 10.0])
+            .with_orientation(Orientation::Horizontal)
+            .with_panes(vec![
+                ("a", 1.0, Pane::new().with_panes(vec!["c", "d"])),
+                ("b", 1.0, Pane::new()),
+            ])
+            .with_constraint(Constraint::new("a", Edge::Width).plus(70.0))
+            .with_constraint(Constraint::new("c", Edge::Height).plus(2.0))
+            .solve_constraints();
+        assert!(root.skipped_constraints().is_empty());
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 70.0, 10.0]);
+        assert_eq!(root["a"]["c"].rect(), [0.0, 0.0, 70.0, 2.0]);
+        assert_eq!(root["a"]["d"].rect(), [0.0, 2.0, 70.0, 8.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "constraints")]
+    fn layout_changes_drop_solved_rects() {
+        let root = constrained().with_margin(5.0);
+        assert_eq!(root["a"].rect(), [5.0, 5.0, 42.5, 0.0]);
+        let root = constrained().with_size([200.0, 10.0]);
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 100.0, 10.0]);
+        let root = constrained().with_orientation(Orientation::Vertical);
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 100.0, 5.0]);
+        let root =
+            constrained().with_breakpoint(Breakpoint::new(Condition::WiderThan(0.0)).hide(1));
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 100.0, 10.0]);
+        let root = constrained().with_layer(1);
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 70.0, 10.0]);
+    }
+
+    fn dock_at(root: Pane, pos: [f64; 2]) -> (Pane, DockTarget) {
+        let target = root.dock_target(pos).expect("dock target");
+        (root.with_docked(&target, Pane::new()), target)
+    }
+
+    #[test]
+    fn dock_beside_sibling() {
+        let (root, target) = dock_at(split(Direction::Forward), [95.0, 5.0]);
+        assert_eq!(
+            (target.path.clone(), target.zone),
+            (vec![1], DropZone::Right)
+        );
+        assert_eq!(root.children().count(), 3);
+        assert_eq!(root[2].rect(), target.rect);
+        assert_eq!(root["a"].rect(), [0.0, 0.0, 50.0, 10.0]);
+        assert_eq!(root["b"].rect(), [50.0, 0.0, 25.0, 10.0]);
+    }
+
+    #[test]
+    fn dock_beside_reversed_sibling() {
+        // "b" is drawn on the left
+        let (root, target) = dock_at(split(Direction::Reverse), [45.0, 5.0]);
+        assert_eq!(
+            (target.path.clone(), target.zone),
+            (vec![1], DropZone::Right)
+        );
+        assert_eq!(target.rect, [25.0, 0.0, 25.0, 10.0]);
+        assert_eq!(root[1].rect(), target.rect);
+        assert_eq!(root["b"].rect(), [0.0, 0.0, 25.0, 10.0]);
+        assert_eq!(root["a"].rect(), [50.0, 0.0, 50.0, 10.0]);
+        let (root, target) = dock_at(split(Direction::Reverse), [5.0, 5.0]);
+        assert_eq!(target.zone, DropZone::Left);
+        assert_eq!(root[2].rect(), target.rect);
+        assert_eq!(root["b"].rect(), [25.0, 0.0, 25.0, 10.0]);
+    }
+
+    #[test]
+    fn dock_remaps_indices() {
+        let root = split(Direction::Forward)
+            .with_breakpoint(Breakpoint::new(Condition::WiderThan(0.0)).hide(1))
+            .with_popup(Popup::new(vec![1], Side::Bottom, Pane::new()));
+        let (root, _) = dock_at(root, [5.0, 5.0]);
+        assert_eq!(root.names["a"], 1);
+        assert_eq!(root.names["b"], 2);
+        assert_eq!(root.breakpoints[0].hidden, vec![2]);
+        assert_eq!(root.popups()[0].anchor, Anchor::Path(vec![2]));
+        assert!(root["b"].is_hidden());
+        let (root, undocked) = root.with_undocked(&[0]);
+        assert!(undocked.is_some());
+        assert_eq!(root.names["a"], 0);
+        assert_eq!(root.breakpoints[0].hidden, vec![1]);
+        assert_eq!(root.popups()[0].anchor, Anchor::Path(vec![1]));
+        let (root, _) = root.with_undocked(&[1]);
+        assert!(root.breakpoints[0].hidden.is_empty());
+        assert!(root.popups().is_empty());
+    }
+
+    #[test]
+    fn dock_wraps_and_undock_collapses() {
+        let root =
+            split(Direction::Forward).with_popup(Popup::new(vec![1], Side::Bottom, Pane::new()));
+        let (root, target) = dock_at(root, [75.0, 1.0]);
+        assert_eq!(target.zone, DropZone::Top);
+        assert!(root[1].wrapper);
+        assert_eq!(root[1].names["b"], 1);
+        assert_eq!(root[1][0].rect(), target.rect);
+        assert_eq!(root.popups()[0].anchor, Anchor::Path(vec![1, 1]));
+        assert_eq!(root.find("b").map(Pane::rect), Some([50.0, 5.0, 50.0, 5.0]));
+        let (root, _) = root.with_undocked(&[1, 0]);
+        assert!(!root[1].wrapper);
+        assert_eq!(root.names["b"], 1);
+        assert_eq!(root["b"].rect(), [50.0, 0.0, 50.0, 10.0]);
+        assert_eq!(root.popups()[0].anchor, Anchor::Path(vec![1]));
+    }
+
+    #[test]
+    fn undock_collapses_tabs() {
+        let (root, target) = dock_at(split(Direction::Forward), [75.0, 5.0]);
+        assert_eq!(target.zone, DropZone::Center);
+        assert_eq!(root[1].active_tab(), Some(1));
+        assert_eq!(root[1].children().count(), 2);
+        let (root, _) = root.with_undocked(&[1, 1]);
+        assert_eq!(root[1].active_tab(), None);
+        assert_eq!(root["b"].rect(), [50.0, 0.0, 50.0, 10.0]);
+        let (root, _) = dock_at(root, [75.0, 5.0]);
+        let (root, _) = root.with_undocked(&[1, 0]);
+        assert!(!root[1].wrapper);
+        assert_eq!(root[1].active_tab(), None);
+        assert!(root.find("b").is_none());
+        assert_eq!(root[1].rect(), [50.0, 0.0, 50.0, 10.0]);
+    }
+}
@@ -150,6 +150,28 @@ impl Pow<Self> for f64 {
     }
 }
 
+/// Trait for rounding numbers to the nearest integer
+pub trait Round {
+    /// The output type
+    type Output;
+    /// Round to the nearest integer
+    fn round(&self) -> Self::Output;
+}
+
+impl Round for f32 {
+    type Output = f32;
+    fn round(&self) -> Self::Output {
+        f32::round(*self)
+    }
+}
+
+impl Round for f64 {
+    type Output = f64;
+    fn round(&self) -> Self::Output {
+        f64::round(*self)
+    }
+}
+
 /// Trait for defining small-number constants
 pub trait ZeroOneTwo: Copy {
     /// Zero `0`
@@ -187,6 +209,7 @@ pub trait Scalar:
     + Sin<Output = Self>
     + Cos<Output = Self>
     + Pow<Self, Output = Self>
+    + Round<Output = Self>
     + ZeroOneTwo
 {
     /// Get the absolute value
@@ -229,6 +252,7 @@ impl<T> Scalar for T where
         + Sin<Output = T>
         + Cos<Output = T>
         + Pow<T, Output = T>
+        + Round<Output = T>
         + ZeroOneTwo
 {
 }
@@ -289,6 +313,15 @@ pub trait Vector2: Copy {
         (self.x().pow(Self::Scalar::TWO) + self.y().pow(Self::Scalar::TWO))
             .pow(Self::Scalar::ONE / Self::Scalar::TWO)
     }
+    /// Linearly interpolate between this vector and another
+    ///
+    /// A `t` of `0` gives this vector and a `t` of `1` gives the other.
+    fn lerp<V: Vector2<Scalar = Self::Scalar>>(self, other: V, t: Self::Scalar) -> Self {
+        Self::new(
+            self.x() + (other.x() - self.x()) * t,
+            self.y() + (other.y() - self.y()) * t,
+        )
+    }
     /// Rotate the vector some number of radians about a pivot
     fn rotate_about<V: Vector2<Scalar = Self::Scalar> + Clone>(
         self,
@@ -389,6 +422,20 @@ pub trait Rectangle: Copy {
     fn center(&self) -> Self::Vector {
         self.top_left().add(self.size().div(Self::Scalar::TWO))
     }
+    /// Check if a point is inside the rectangle
+    fn contains<V: Vector2<Scalar = Self::Scalar>>(&self, point: V) -> bool {
+        point.x() >= self.left()
+            && point.x() < self.right()
+            && point.y() >= self.top()
+            && point.y() < self.bottom()
+    }
+    /// Check if another rectangle is entirely inside this one
+    fn contains_rect<R: Rectangle<Scalar = Self::Scalar>>(&self, other: R) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() >= self.top()
+            && other.bottom() <= self.bottom()
+    }
     /// Transform the rectangle into one with a different top-left corner position
     fn with_top_left(self, top_left: Self::Vector) -> Self {
         Self::new(top_left, self.size())
@@ -397,6 +444,35 @@ pub trait Rectangle: Copy {
     fn with_size(self, size: Self::Vector) -> Self {
         Self::new(self.top_left(), size)
     }
+    /// Linearly interpolate between this rectangle and another
+    ///
+    /// A `t` of `0` gives this rectangle and a `t` of `1` gives the other.
+    fn lerp(self, other: Self, t: Self::Scalar) -> Self {
+        Self::new(
+            self.top_left().lerp(other.top_left(), t),
+            self.size().lerp(other.size(), t),
+        )
+    }
+    /// Round each of the rectangle's edges to the nearest multiple of the given pixel size
+    ///
+    /// Because edges are rounded rather than sizes, rectangles which share an edge
+    /// before snapping will still share it afterwards.
+    ///
+    /// A pixel size which is not positive, such as zero or NaN, leaves the rectangle as is.
+    fn snapped(self, pixel_size: Self::Scalar) -> Self {
+        if pixel_size.partial_cmp(&Self::Scalar::ZERO) != Some(std::cmp::Ordering::Greater) {
+            return self;
+        }
+        let snap = |x: Self::Scalar| (x / pixel_size).round() * pixel_size;
+        let left = snap(self.left());
+        let top = snap(self.top());
+        let right = snap(self.right());
+        let bottom = snap(self.bottom());
+        Self::new(
+            Self::Vector::new(left, top),
+            Self::Vector::new(right - left, bottom - top),
+        )
+    }
 }
 
 impl<P> Rectangle for P
@@ -0,0 +1,183 @@
+use crate::math::{Rectangle, Scalar, Vector2, ZeroOneTwo};
+use crate::Pane;
+
+/// A way of identifying the `Pane` that a `Popup` is anchored to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Anchor {
+    /// The `Pane` with the given name, as found by `Pane::find`
+    Name(String),
+    /// The `Pane` at the given path of child indices, as in `Hit::Tree`
+    Path(Vec<usize>),
+}
+
+impl<'a> From<&'a str> for Anchor {
+    fn from(name: &'a str) -> Self {
+        Anchor::Name(name.into())
+    }
+}
+
+impl From<String> for Anchor {
+    fn from(name: String) -> Self {
+        Anchor::Name(name)
+    }
+}
+
+impl From<Vec<usize>> for Anchor {
+    fn from(path: Vec<usize>) -> Self {
+        Anchor::Path(path)
+    }
+}
+
+/// A side of a rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Side {
+    /// Above the rectangle
+    Top,
+    /// Below the rectangle
+    Bottom,
+    /// Left of the rectangle
+    Left,
+    /// Right of the rectangle
+    Right,
+}
+
+impl Side {
+    /// Get the opposite side
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// A transient `Pane` such as a tooltip or menu which is placed next to another `Pane`
+///
+/// Popups do not take part in the layout of the `Pane` they are added to.
+/// Instead, each popup keeps the size of its own `Pane` and is placed on its
+/// preferred side of its anchor. If it would leave the rectangle of the `Pane`
+/// it was added to, it is flipped to the opposite side and then shifted
+/// to stay inside.
+#[derive(Debug, Clone)]
+pub struct Popup<R = [f64; 4]>
+where
+    R: Rectangle,
+{
+    pub(crate) anchor: Anchor,
+    pub(crate) side: Side,
+    pub(crate) gap: R::Scalar,
+    pub(crate) pane: Pane<R>,
+    pub(crate) placed_side: Option<Side>,
+}
+
+impl<R> Popup<R>
+where
+    R: Rectangle,
+{
+    /// Create a new `Popup` anchored to a `Pane` on the given side
+    pub fn new<A>(anchor: A, side: Side, pane: Pane<R>) -> Self
+    where
+        A: Into<Anchor>,
+    {
+        Popup {
+            anchor: anchor.into(),
+            side,
+            gap: R::Scalar::ZERO,
+            pane,
+            placed_side: None,
+        }
+    }
+    /// Get the anchor
+    pub fn anchor(&self) -> &Anchor {
+        &self.anchor
+    }
+    /// Get the preferred side
+    pub fn side(&self) -> Side {
+        self.side
+    }
+    /// Set the distance between the popup and its anchor
+    pub fn with_gap(mut self, gap: R::Scalar) -> Self {
+        self.gap = gap;
+        self
+    }
+    /// Get the distance between the popup and its anchor
+    pub fn gap(&self) -> R::Scalar {
+        self.gap
+    }
+    /// Get the popup's `Pane`
+    pub fn pane(&self) -> &Pane<R> {
+        &self.pane
+    }
+    /// Get the side the popup was placed on
+    ///
+    /// This is `None` if the anchor could not be found.
+    pub fn placed_side(&self) -> Option<Side> {
+        self.placed_side
+    }
+    /// Place the popup next to an anchor rectangle inside some bounds
+    pub(crate) fn place(&mut self, anchor: Option<R>, bounds: R) {
+        let anchor = match anchor {
+            Some(anchor) => anchor,
+            None => {
+                self.placed_side = None;
+                return;
+            }
+        };
+        let size = self.pane.size();
+        let side = if !self.fits(self.side, anchor, bounds)
+            && self.fits(self.side.opposite(), anchor, bounds)
+        {
+            self.side.opposite()
+        } else {
+            self.side
+        };
+        let top_left = self.top_left(side, anchor);
+        let x = top_left
+            .x()
+            .min(bounds.right() - size.x())
+            .max(bounds.left());
+        let y = top_left
+            .y()
+            .min(bounds.bottom() - size.y())
+            .max(bounds.top());
+        self.pane.rect = R::new(R::Vector::new(x, y), size);
+        self.pane.update_rects();
+        self.placed_side = Some(side);
+    }
+    fn top_left(&self, side: Side, anchor: R) -> R::Vector {
+        let size = self.pane.size();
+        match side {
+            Side::Top => R::Vector::new(anchor.left(), anchor.top() - self.gap - size.y()),
+            Side::Bottom => R::Vector::new(anchor.left(), anchor.bottom() + self.gap),
+            Side::Left => R::Vector::new(anchor.left() - self.gap - size.x(), anchor.top()),
+            Side::Right => R::Vector::new(anchor.right() + self.gap, anchor.top()),
+        }
+    }
+    /// Check if the popup fits inside the bounds on a side along the axis it is placed on
+    fn fits(&self, side: Side, anchor: R, bounds: R) -> bool {
+        let top_left = self.top_left(side, anchor);
+        let size = self.pane.size();
+        match side {
+            Side::Top | Side::Bottom => {
+                top_left.y() >= bounds.top() && top_left.y() + size.y() <= bounds.bottom()
+            }
+            Side::Left | Side::Right => {
+                top_left.x() >= bounds.left() && top_left.x() + size.x() <= bounds.right()
+            }
+        }
+    }
+}
+
+/// The result of a hit test
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Hit {
+    /// A `Pane` in the main tree at the given path of child indices
+    Tree(Vec<usize>),
+    /// A hit in a popup
+    ///
+    /// The fields are the path of child indices to the `Pane` in the main tree
+    /// which has the popup, the index of the popup, and the hit in the popup's `Pane`.
+    Popup(Vec<usize>, usize, Box<Hit>),
+}
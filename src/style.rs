@@ -0,0 +1,1126 @@
+use std::{
+    cmp::Ordering,
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Arc,
+    },
+};
+#[cfg(feature = "graphics")]
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+#[cfg(feature = "graphics")]
+use graphics::{
+    draw_state::DrawState,
+    math::{transform_pos, Matrix2d},
+    rectangle::{Border as GraphicsBorder, Shape},
+    triangulation::{tx, ty},
+    Graphics, Image, ImageSize, Polygon, Rectangle as GraphicsRectangle, Viewport,
+};
+
+use crate::math::{Rectangle, Scalar, Vector2};
+#[cfg(feature = "graphics")]
+use crate::texture::TextureSource;
+use crate::{color, Color};
+
+/// A border drawn along the inside edges of a `Pane`
+///
+/// A border's width counts toward the space between a `Pane`'s
+/// edges and its contents, so contents never overlap it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Border<S>
+where
+    S: Scalar,
+{
+    /// The width of the border
+    pub width: S,
+    /// The color of the border
+    pub color: Color,
+    /// Whether the border is drawn on the left edge
+    pub left: bool,
+    /// Whether the border is drawn on the right edge
+    pub right: bool,
+    /// Whether the border is drawn on the top edge
+    pub top: bool,
+    /// Whether the border is drawn on the bottom edge
+    pub bottom: bool,
+}
+
+impl<S> Border<S>
+where
+    S: Scalar,
+{
+    /// Create a new `Border` on all edges
+    pub fn new(width: S, color: Color) -> Self {
+        Border {
+            width,
+            color,
+            left: true,
+            right: true,
+            top: true,
+            bottom: true,
+        }
+    }
+    /// Set the width
+    pub fn width(mut self, width: S) -> Self {
+        self.width = width;
+        self
+    }
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+    /// Set whether the border is drawn on the left edge
+    pub fn left(mut self, left: bool) -> Self {
+        self.left = left;
+        self
+    }
+    /// Set whether the border is drawn on the right edge
+    pub fn right(mut self, right: bool) -> Self {
+        self.right = right;
+        self
+    }
+    /// Set whether the border is drawn on the top edge
+    pub fn top(mut self, top: bool) -> Self {
+        self.top = top;
+        self
+    }
+    /// Set whether the border is drawn on the bottom edge
+    pub fn bottom(mut self, bottom: bool) -> Self {
+        self.bottom = bottom;
+        self
+    }
+    /// Check if the border is drawn on all four edges
+    pub fn is_full(&self) -> bool {
+        self.left && self.right && self.top && self.bottom
+    }
+    /// Get the space taken up by the border on the top-left and bottom-right sides
+    pub fn insets<V>(&self) -> (V, V)
+    where
+        V: Vector2<Scalar = S>,
+    {
+        let edge = |present: bool| if present { self.width } else { S::ZERO };
+        (
+            V::new(edge(self.left), edge(self.top)),
+            V::new(edge(self.right), edge(self.bottom)),
+        )
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl Border<f64> {
+    /// Draw the border inside a rectangle with the given corner radius
+    ///
+    /// Rounded corners are only drawn if the border is on all four edges.
+    pub(crate) fn draw<R, G>(
+        &self,
+        rect: R,
+        corner_radius: f64,
+        draw_state: &DrawState,
+        transform: Matrix2d,
+        graphics: &mut G,
+    ) where
+        R: Rectangle<Scalar = f64>,
+        G: Graphics,
+    {
+        if self.width <= 0.0 || self.color[3] == 0.0 {
+            return;
+        }
+        let half = self.width / 2.0;
+        if self.is_full() {
+            let inner = [
+                rect.left() + half,
+                rect.top() + half,
+                rect.width() - self.width,
+                rect.height() - self.width,
+            ];
+            let shape = if corner_radius > 0.0 {
+                Shape::Round((corner_radius - half).max(0.0), 32)
+            } else {
+                Shape::Square
+            };
+            GraphicsRectangle::new([0.0; 4])
+                .shape(shape)
+                .border(GraphicsBorder {
+                    color: self.color,
+                    radius: half,
+                })
+                .draw(inner, draw_state, transform, graphics);
+            return;
+        }
+        let strip = GraphicsRectangle::new(self.color);
+        let w = self.width;
+        let strips = [
+            (self.left, [rect.left(), rect.top(), w, rect.height()]),
+            (self.right, [rect.right() - w, rect.top(), w, rect.height()]),
+            (self.top, [rect.left(), rect.top(), rect.width(), w]),
+            (
+                self.bottom,
+                [rect.left(), rect.bottom() - w, rect.width(), w],
+            ),
+        ];
+        for (present, strip_rect) in strips.iter() {
+            if *present {
+                strip.draw(*strip_rect, draw_state, transform, graphics);
+            }
+        }
+    }
+}
+
+/// Draw a filled rectangle with the given corner radius
+#[cfg(feature = "graphics")]
+pub(crate) fn fill_rect<R, G>(
+    color: Color,
+    rect: R,
+    corner_radius: f64,
+    draw_state: &DrawState,
+    transform: Matrix2d,
+    graphics: &mut G,
+) where
+    R: Rectangle<Scalar = f64>,
+    G: Graphics,
+{
+    let shape = if corner_radius > 0.0 {
+        Shape::Round(corner_radius, 32)
+    } else {
+        Shape::Square
+    };
+    GraphicsRectangle::new(color).shape(shape).draw(
+        rect.map::<[f64; 4]>(),
+        draw_state,
+        transform,
+        graphics,
+    );
+}
+
+/// Get the largest corner radius that fits in a rectangle
+pub(crate) fn clamp_radius<R>(rect: R, radius: f64) -> f64
+where
+    R: Rectangle<Scalar = f64>,
+{
+    radius
+        .min(rect.width() / 2.0)
+        .min(rect.height() / 2.0)
+        .max(0.0)
+}
+
+/// A policy for drawing contents and children which do not fit inside a `Pane`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Overflow {
+    /// Draw everything, even outside the `Pane`
+    #[default]
+    Visible,
+    /// Cut off anything outside the `Pane` at its edges
+    Clip,
+    /// Do not draw lines of text or children which do not
+    /// fit entirely inside the `Pane`
+    Hidden,
+}
+
+/// Get a scissor rectangle which covers a rectangle, combined with an existing scissor
+///
+/// With a viewport, the rectangle is mapped through the transform into pixels
+/// of the render target. Without one, its units are assumed to be pixels.
+#[cfg(feature = "graphics")]
+pub(crate) fn scissor<R>(
+    rect: R,
+    transform: Matrix2d,
+    viewport: Option<Viewport>,
+    existing: Option<[u32; 4]>,
+) -> [u32; 4]
+where
+    R: Rectangle<Scalar = f64>,
+{
+    let (left, top, right, bottom) = match viewport {
+        Some(viewport) => {
+            // The transform maps to normalized device coordinates, which the
+            // viewport maps to pixels with a bottom-left origin
+            let [x, y, width, height] = viewport.rect;
+            let draw_height = f64::from(viewport.draw_size[1]);
+            let pixels: Vec<[f64; 2]> = [
+                rect.top_left(),
+                rect.top_right(),
+                rect.bottom_left(),
+                rect.bottom_right(),
+            ]
+            .iter()
+            .map(|corner| {
+                let [nx, ny] = transform_pos(transform, [corner.x(), corner.y()]);
+                [
+                    f64::from(x) + (nx + 1.0) / 2.0 * f64::from(width),
+                    draw_height - (f64::from(y) + (ny + 1.0) / 2.0 * f64::from(height)),
+                ]
+            })
+            .collect();
+            let min = |i: usize| pixels.iter().map(|p| p[i]).fold(f64::INFINITY, f64::min);
+            let max = |i: usize| {
+                pixels
+                    .iter()
+                    .map(|p| p[i])
+                    .fold(f64::NEG_INFINITY, f64::max)
+            };
+            (min(0), min(1), max(0), max(1))
+        }
+        None => (rect.left(), rect.top(), rect.right(), rect.bottom()),
+    };
+    let left = left.floor().max(0.0) as u32;
+    let top = top.floor().max(0.0) as u32;
+    let right = right.ceil().max(0.0) as u32;
+    let bottom = bottom.ceil().max(0.0) as u32;
+    let (left, top, right, bottom) = match existing {
+        Some([x, y, w, h]) => (left.max(x), top.max(y), right.min(x + w), bottom.min(y + h)),
+        None => (left, top, right, bottom),
+    };
+    [
+        left,
+        top,
+        right.saturating_sub(left),
+        bottom.saturating_sub(top),
+    ]
+}
+
+/// A drop shadow drawn beneath a `Pane`
+///
+/// Shadows do not affect layout.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Shadow<S>
+where
+    S: Scalar,
+{
+    /// The offset of the shadow from the `Pane`
+    pub offset: [S; 2],
+    /// The distance over which the shadow's edges fade out
+    pub blur: S,
+    /// The distance by which the shadow is larger than the `Pane`
+    pub spread: S,
+    /// The color of the shadow
+    pub color: Color,
+}
+
+impl<S> Shadow<S>
+where
+    S: Scalar,
+{
+    /// Create a new `Shadow` with the given offset and no blur or spread
+    pub fn new(offset: [S; 2], color: Color) -> Self {
+        Shadow {
+            offset,
+            blur: S::ZERO,
+            spread: S::ZERO,
+            color,
+        }
+    }
+    /// Set the offset
+    pub fn offset(mut self, offset: [S; 2]) -> Self {
+        self.offset = offset;
+        self
+    }
+    /// Set the blur distance
+    pub fn blur(mut self, blur: S) -> Self {
+        self.blur = blur;
+        self
+    }
+    /// Set the spread distance
+    pub fn spread(mut self, spread: S) -> Self {
+        self.spread = spread;
+        self
+    }
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Shadow<f64> {
+    /// Get the alpha of the shadow of a rectangle with the
+    /// given corner radius at the given point
+    ///
+    /// Across the blur, which is centered on the edge of the shadow,
+    /// the alpha falls off linearly from the shadow's alpha to zero.
+    pub fn alpha_at<R, V>(&self, rect: R, corner_radius: f64, point: V) -> f32
+    where
+        R: Rectangle<Scalar = f64>,
+        V: Vector2<Scalar = f64>,
+    {
+        let shape = [
+            rect.left() + self.offset[0] - self.spread,
+            rect.top() + self.offset[1] - self.spread,
+            rect.width() + 2.0 * self.spread,
+            rect.height() + 2.0 * self.spread,
+        ];
+        let blur = self.blur.max(0.0);
+        if (shape[2] <= 0.0 || shape[3] <= 0.0) && blur == 0.0 {
+            return 0.0;
+        }
+        // Get the signed distance from the edge of the rounded shape,
+        // treating a shape with no size as a line or point
+        let half = [shape[2].max(0.0) / 2.0, shape[3].max(0.0) / 2.0];
+        let radius = clamp_radius(shape, corner_radius + self.spread);
+        let dx = (point.x() - (shape[0] + shape[2] / 2.0)).abs() - half[0] + radius;
+        let dy = (point.y() - (shape[1] + shape[3] / 2.0)).abs() - half[1] + radius;
+        let distance = dx.max(0.0).hypot(dy.max(0.0)) + dx.max(dy).min(0.0) - radius;
+        let coverage = if blur > 0.0 {
+            (0.5 - distance / blur).clamp(0.0, 1.0)
+        } else if distance <= 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        self.color[3] * coverage as f32
+    }
+    /// Rasterize the shadow of a rectangle with the given corner radius
+    /// into an image of the given size
+    ///
+    /// Each pixel is one unit wide and sampled at its center, with the
+    /// image's top-left corner at the origin of the rectangle's coordinates.
+    ///
+    /// Returns `None` if the image would be too large
+    pub fn rasterize<R>(
+        &self,
+        rect: R,
+        corner_radius: f64,
+        width: u32,
+        height: u32,
+    ) -> Option<ImageFill>
+    where
+        R: Rectangle<Scalar = f64>,
+    {
+        let len = width.checked_mul(height)?;
+        let mut pixels = Vec::with_capacity(len as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let point = [f64::from(x) + 0.5, f64::from(y) + 0.5];
+                let mut color = self.color;
+                color[3] = self.alpha_at(rect, corner_radius, point);
+                pixels.push(color);
+            }
+        }
+        ImageFill::new(width, height, pixels)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl Shadow<f64> {
+    /// Draw the shadow of a rectangle with the given corner radius
+    ///
+    /// The blur is approximated by stacking translucent layers
+    /// of increasing size, about one per unit of blur.
+    pub(crate) fn draw<R, G>(
+        &self,
+        rect: R,
+        corner_radius: f64,
+        draw_state: &DrawState,
+        transform: Matrix2d,
+        graphics: &mut G,
+    ) where
+        R: Rectangle<Scalar = f64>,
+        G: Graphics,
+    {
+        if self.color[3] == 0.0 {
+            return;
+        }
+        let blur = self.blur.max(0.0);
+        let layers = blur.ceil().clamp(1.0, 32.0) as usize;
+        // Choose the alpha of each layer so that they stack up to the shadow's alpha
+        let mut color = self.color;
+        color[3] = 1.0 - (1.0 - self.color[3]).powf(1.0 / layers as f32);
+        for i in 0..layers {
+            let expansion = if layers > 1 {
+                self.spread + blur * (0.5 - i as f64 / (layers - 1) as f64)
+            } else {
+                self.spread
+            };
+            let layer = [
+                rect.left() + self.offset[0] - expansion,
+                rect.top() + self.offset[1] - expansion,
+                rect.width() + 2.0 * expansion,
+                rect.height() + 2.0 * expansion,
+            ];
+            if layer[2] <= 0.0 || layer[3] <= 0.0 {
+                continue;
+            }
+            let radius = clamp_radius(layer, corner_radius + expansion);
+            fill_rect(color, layer, radius, draw_state, transform, graphics);
+        }
+    }
+}
+
+/// A divider drawn in the gutters between a `Pane`'s children
+///
+/// Splitters do not affect layout. They are centered in the space
+/// that a `Pane`'s margin leaves between its children.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Splitter<S>
+where
+    S: Scalar,
+{
+    /// The color of the splitter
+    pub color: Color,
+    /// The color of the splitter while it is hovered over
+    pub hover_color: Option<Color>,
+    /// The thickness of the splitter
+    pub thickness: S,
+}
+
+impl<S> Splitter<S>
+where
+    S: Scalar,
+{
+    /// Create a new `Splitter` with the given color and a thickness of one unit
+    pub fn new(color: Color) -> Self {
+        Splitter {
+            color,
+            hover_color: None,
+            thickness: S::ONE,
+        }
+    }
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+    /// Set the color used while the splitter is hovered over
+    pub fn hover_color(mut self, hover_color: Color) -> Self {
+        self.hover_color = Some(hover_color);
+        self
+    }
+    /// Set the thickness
+    pub fn thickness(mut self, thickness: S) -> Self {
+        self.thickness = thickness;
+        self
+    }
+    /// Get the color to draw the splitter with
+    pub fn current_color(&self, hovered: bool) -> Color {
+        match (hovered, self.hover_color) {
+            (true, Some(hover_color)) => hover_color,
+            _ => self.color,
+        }
+    }
+}
+
+/// A color at some position along a gradient
+///
+/// The position is in the range `[0, 1]`.
+pub type ColorStop = (f32, Color);
+
+/// Get the color at some position along a gradient
+pub fn gradient_color(stops: &[ColorStop], t: f32) -> Color {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return color::TRANSPARENT,
+    };
+    if t <= first.0 {
+        return first.1;
+    }
+    for pair in stops.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if t <= to.0 {
+            let span = to.0 - from.0;
+            return if span > 0.0 {
+                color::lerp(from.1, to.1, (t - from.0) / span)
+            } else {
+                to.1
+            };
+        }
+    }
+    last.1
+}
+
+/// Sort color stops by their positions
+fn sort_stops(stops: &mut [ColorStop]) {
+    stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+}
+
+/// The id of the next `ImageFill` to be created
+///
+/// Ids with the highest bit set are reserved for rasterized gradients.
+static NEXT_IMAGE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An RGBA image which can fill a `Pane`'s background
+///
+/// Images are drawn with textures from a `TextureSource`. Each new image gets
+/// its own texture, while clones of an image share the same texture.
+#[derive(Debug, Clone)]
+pub struct ImageFill {
+    #[cfg_attr(not(feature = "graphics"), allow(dead_code))]
+    id: u64,
+    width: u32,
+    height: u32,
+    pixels: Arc<Vec<Color>>,
+}
+
+impl PartialEq for ImageFill {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.pixels == other.pixels
+    }
+}
+
+impl ImageFill {
+    /// Create a new `ImageFill` from a row-major list of pixel colors
+    ///
+    /// Returns `None` if the number of pixels does not match the size
+    pub fn new(width: u32, height: u32, pixels: Vec<Color>) -> Option<Self> {
+        let len = width.checked_mul(height)?;
+        if pixels.len() as u64 == u64::from(len) {
+            Some(ImageFill {
+                id: NEXT_IMAGE_ID.fetch_add(1, AtomicOrdering::Relaxed),
+                width,
+                height,
+                pixels: Arc::new(pixels),
+            })
+        } else {
+            None
+        }
+    }
+    /// Create a new `ImageFill` from row-major 8-bit RGBA data
+    ///
+    /// Returns `None` if the length of the data does not match the size
+    pub fn from_rgba8(width: u32, height: u32, data: &[u8]) -> Option<Self> {
+        let pixels = data
+            .chunks(4)
+            .filter(|chunk| chunk.len() == 4)
+            .map(|p| {
+                [
+                    f32::from(p[0]) / 255.0,
+                    f32::from(p[1]) / 255.0,
+                    f32::from(p[2]) / 255.0,
+                    f32::from(p[3]) / 255.0,
+                ]
+            })
+            .collect();
+        ImageFill::new(width, height, pixels)
+    }
+    /// Get the width in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Get the height in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Get the color of a pixel
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        self.pixels[(y * self.width + x) as usize]
+    }
+    /// Get the image as row-major 8-bit RGBA data
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| {
+                pixel
+                    .iter()
+                    .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+            })
+            .collect()
+    }
+}
+
+/// A way of fitting an image into a `Pane`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageMode {
+    /// Stretch the image to fill the `Pane`
+    Stretch,
+    /// Repeat the image at its original size, starting from the top-left corner
+    Tile,
+    /// Keep the corners at their original size, stretch the edges along
+    /// their length, and stretch the center to fill the rest
+    ///
+    /// The values are the sizes of the left, right, top,
+    /// and bottom slices in image pixels.
+    NineSlice(u32, u32, u32, u32),
+}
+
+/// A background which is drawn over a `Pane`'s color
+#[derive(Debug, Clone, PartialEq)]
+pub enum Background<S>
+where
+    S: Scalar,
+{
+    /// A linear gradient
+    LinearGradient {
+        /// The direction of the gradient in radians, where `0` goes
+        /// from left to right and positive angles turn clockwise
+        angle: S,
+        /// The color stops, sorted by position
+        stops: Vec<ColorStop>,
+    },
+    /// A radial gradient which ends at the corner farthest from its center
+    RadialGradient {
+        /// The center of the gradient as a fraction of the `Pane`'s size
+        center: [S; 2],
+        /// The color stops, sorted by position
+        stops: Vec<ColorStop>,
+    },
+    /// An image
+    Image(ImageFill, ImageMode),
+}
+
+impl<S> Background<S>
+where
+    S: Scalar,
+{
+    /// Create a new linear gradient from a list of color stops
+    ///
+    /// The stops are sorted by position.
+    pub fn linear<I>(angle: S, stops: I) -> Self
+    where
+        I: IntoIterator<Item = ColorStop>,
+    {
+        let mut stops: Vec<ColorStop> = stops.into_iter().collect();
+        sort_stops(&mut stops);
+        Background::LinearGradient { angle, stops }
+    }
+    /// Create a new radial gradient centered in the `Pane` from a list of color stops
+    ///
+    /// The stops are sorted by position.
+    pub fn radial<I>(stops: I) -> Self
+    where
+        I: IntoIterator<Item = ColorStop>,
+    {
+        let half = S::ONE / S::TWO;
+        let mut stops: Vec<ColorStop> = stops.into_iter().collect();
+        sort_stops(&mut stops);
+        Background::RadialGradient {
+            center: [half, half],
+            stops,
+        }
+    }
+    /// Create a new image background
+    pub fn image(image: ImageFill, mode: ImageMode) -> Self {
+        Background::Image(image, mode)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl Background<f64> {
+    /// Draw the background inside a rectangle with the given corner radius
+    ///
+    /// Images and radial gradients are only drawn if the
+    /// texture source can provide textures for them.
+    pub(crate) fn draw<R, X, G>(
+        &self,
+        rect: R,
+        corner_radius: f64,
+        textures: &mut X,
+        draw_state: &DrawState,
+        transform: Matrix2d,
+        graphics: &mut G,
+    ) where
+        R: Rectangle<Scalar = f64>,
+        X: TextureSource<Texture = G::Texture>,
+        G: Graphics,
+    {
+        let mut fill = Fill {
+            rect: rect.map::<[f64; 4]>(),
+            corner_radius,
+            outline: rounded_outline(rect.map::<[f64; 4]>(), corner_radius),
+            draw_state,
+            transform,
+            graphics,
+        };
+        match self {
+            Background::LinearGradient { angle, stops } => {
+                draw_linear_gradient(*angle, stops, &mut fill)
+            }
+            Background::RadialGradient { center, stops } => {
+                draw_radial_gradient(*center, stops, textures, &mut fill)
+            }
+            Background::Image(image, mode) => draw_image(image, *mode, textures, &mut fill),
+        }
+    }
+}
+
+/// Clip a convex polygon to the half-plane where `dot(point, dir) >= min`
+#[cfg(feature = "graphics")]
+fn clip_polygon(polygon: &[[f64; 2]], dir: [f64; 2], min: f64) -> Vec<[f64; 2]> {
+    let dist = |p: [f64; 2]| p[0] * dir[0] + p[1] * dir[1] - min;
+    let mut clipped = Vec::new();
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (da, db) = (dist(a), dist(b));
+        if da >= 0.0 {
+            clipped.push(a);
+        }
+        if (da >= 0.0) != (db >= 0.0) {
+            let t = da / (da - db);
+            clipped.push([a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]);
+        }
+    }
+    clipped
+}
+
+/// Get the corners of a rectangle as a polygon
+#[cfg(feature = "graphics")]
+fn rect_polygon(rect: [f64; 4]) -> Vec<[f64; 2]> {
+    vec![
+        [rect[0], rect[1]],
+        [rect[0] + rect[2], rect[1]],
+        [rect[0] + rect[2], rect[1] + rect[3]],
+        [rect[0], rect[1] + rect[3]],
+    ]
+}
+
+/// Get the outline of a rectangle with rounded corners as a convex polygon
+#[cfg(feature = "graphics")]
+fn rounded_outline(rect: [f64; 4], radius: f64) -> Vec<[f64; 2]> {
+    if radius <= 0.0 {
+        return rect_polygon(rect);
+    }
+    const SEGMENTS: usize = 8;
+    let quarter = std::f64::consts::FRAC_PI_2;
+    let corners = [
+        (rect[0] + rect[2] - radius, rect[1] + radius, -quarter),
+        (rect[0] + rect[2] - radius, rect[1] + rect[3] - radius, 0.0),
+        (rect[0] + radius, rect[1] + rect[3] - radius, quarter),
+        (rect[0] + radius, rect[1] + radius, 2.0 * quarter),
+    ];
+    let mut outline = Vec::new();
+    for &(x, y, start) in &corners {
+        for i in 0..=SEGMENTS {
+            let angle = start + quarter * i as f64 / SEGMENTS as f64;
+            outline.push([x + radius * angle.cos(), y + radius * angle.sin()]);
+        }
+    }
+    outline
+}
+
+/// Clip a convex polygon to the inside of a convex outline
+#[cfg(feature = "graphics")]
+fn clip_to_outline(mut polygon: Vec<[f64; 2]>, outline: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let n = outline.len() as f64;
+    let center = outline
+        .iter()
+        .fold([0.0, 0.0], |c, p| [c[0] + p[0] / n, c[1] + p[1] / n]);
+    for (i, &a) in outline.iter().enumerate() {
+        let b = outline[(i + 1) % outline.len()];
+        let mut normal = [a[1] - b[1], b[0] - a[0]];
+        if (center[0] - a[0]) * normal[0] + (center[1] - a[1]) * normal[1] < 0.0 {
+            normal = [-normal[0], -normal[1]];
+        }
+        polygon = clip_polygon(&polygon, normal, a[0] * normal[0] + a[1] * normal[1]);
+        if polygon.len() < 3 {
+            break;
+        }
+    }
+    polygon
+}
+
+/// The area being filled by a background
+#[cfg(feature = "graphics")]
+struct Fill<'a, G> {
+    rect: [f64; 4],
+    corner_radius: f64,
+    outline: Vec<[f64; 2]>,
+    draw_state: &'a DrawState,
+    transform: Matrix2d,
+    graphics: &'a mut G,
+}
+
+#[cfg(feature = "graphics")]
+impl<'a, G> Fill<'a, G>
+where
+    G: Graphics,
+{
+    /// Fill a convex polygon, clipped to the area
+    fn polygon(&mut self, color: Color, polygon: Vec<[f64; 2]>) {
+        let polygon = clip_to_outline(polygon, &self.outline);
+        if polygon.len() >= 3 {
+            Polygon::new(color).draw(&polygon, self.draw_state, self.transform, self.graphics);
+        }
+    }
+    /// Check if a rectangle overlaps one of the area's rounded corners
+    fn in_corner(&self, rect: [f64; 4]) -> bool {
+        let r = self.corner_radius;
+        let (left, top) = (self.rect[0] + r, self.rect[1] + r);
+        let (right, bottom) = (
+            self.rect[0] + self.rect[2] - r,
+            self.rect[1] + self.rect[3] - r,
+        );
+        r > 0.0
+            && (rect[0] < left || rect[0] + rect[2] > right)
+            && (rect[1] < top || rect[1] + rect[3] > bottom)
+    }
+    /// Draw regions of a texture into destination rectangles, clipped to the area
+    ///
+    /// Each region is a destination rectangle and a source rectangle in texture pixels.
+    fn images(&mut self, texture: &G::Texture, regions: &[([f64; 4], [f64; 4])]) {
+        let mut unclipped = Vec::new();
+        for &(dest, source) in regions {
+            if dest[2] <= 0.0 || dest[3] <= 0.0 {
+                continue;
+            }
+            let cropped = match intersect(dest, self.rect) {
+                Some(cropped) => cropped,
+                None => continue,
+            };
+            let scale = [source[2] / dest[2], source[3] / dest[3]];
+            let source = [
+                source[0] + (cropped[0] - dest[0]) * scale[0],
+                source[1] + (cropped[1] - dest[1]) * scale[1],
+                cropped[2] * scale[0],
+                cropped[3] * scale[1],
+            ];
+            if self.in_corner(cropped) {
+                self.clipped_image(texture, cropped, source);
+            } else {
+                unclipped.push((cropped, source));
+            }
+        }
+        if let [(dest, source)] = unclipped[..] {
+            Image::new().rect(dest).src_rect(source).draw(
+                texture,
+                self.draw_state,
+                self.transform,
+                self.graphics,
+            );
+        } else if !unclipped.is_empty() {
+            graphics::image::draw_many(
+                &unclipped,
+                [1.0; 4],
+                texture,
+                self.draw_state,
+                self.transform,
+                self.graphics,
+            );
+        }
+    }
+    /// Draw a region of a texture into a rectangle, clipped to the area's outline
+    fn clipped_image(&mut self, texture: &G::Texture, dest: [f64; 4], source: [f64; 4]) {
+        let polygon = clip_to_outline(rect_polygon(dest), &self.outline);
+        if polygon.len() < 3 {
+            return;
+        }
+        let (width, height) = texture.get_size();
+        let transform = self.transform;
+        let vertex = |p: [f64; 2]| {
+            let uv = [
+                (source[0] + (p[0] - dest[0]) / dest[2] * source[2]) / f64::from(width),
+                (source[1] + (p[1] - dest[1]) / dest[3] * source[3]) / f64::from(height),
+            ];
+            (
+                [tx(transform, p[0], p[1]), ty(transform, p[0], p[1])],
+                [uv[0] as f32, uv[1] as f32],
+            )
+        };
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        for i in 1..polygon.len() - 1 {
+            for &p in &[polygon[0], polygon[i], polygon[i + 1]] {
+                let (position, uv) = vertex(p);
+                positions.push(position);
+                uvs.push(uv);
+            }
+        }
+        self.graphics
+            .tri_list_uv(self.draw_state, &[1.0; 4], texture, |f| f(&positions, &uvs));
+    }
+}
+
+/// Draw a linear gradient as a series of bands perpendicular to its direction
+#[cfg(feature = "graphics")]
+fn draw_linear_gradient<G>(angle: f64, stops: &[ColorStop], fill: &mut Fill<G>)
+where
+    G: Graphics,
+{
+    let dir = [angle.cos(), angle.sin()];
+    let corners = rect_polygon(fill.rect);
+    let projections: Vec<f64> = corners
+        .iter()
+        .map(|p| p[0] * dir[0] + p[1] * dir[1])
+        .collect();
+    let start = projections.iter().cloned().fold(f64::INFINITY, f64::min);
+    let end = projections
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let length = end - start;
+    let bands = (length / 2.0).ceil().clamp(1.0, 256.0) as usize;
+    for i in 0..bands {
+        let lo = start + length * i as f64 / bands as f64;
+        let hi = start + length * (i + 1) as f64 / bands as f64;
+        let band = clip_polygon(&corners, dir, lo);
+        let band = clip_polygon(&band, [-dir[0], -dir[1]], -hi);
+        let t = (i as f32 + 0.5) / bands as f32;
+        fill.polygon(gradient_color(stops, t), band);
+    }
+}
+
+/// The number of pixels along the longer side of a rasterized radial gradient
+#[cfg(feature = "graphics")]
+const GRADIENT_RESOLUTION: f64 = 128.0;
+
+/// Draw a radial gradient by stretching a rasterized texture of it over the area
+#[cfg(feature = "graphics")]
+fn draw_radial_gradient<X, G>(
+    center: [f64; 2],
+    stops: &[ColorStop],
+    textures: &mut X,
+    fill: &mut Fill<G>,
+) where
+    X: TextureSource<Texture = G::Texture>,
+    G: Graphics,
+{
+    let rect = fill.rect;
+    if rect[2] <= 0.0 || rect[3] <= 0.0 {
+        return;
+    }
+    let scale = GRADIENT_RESOLUTION / rect[2].max(rect[3]);
+    let width = (rect[2] * scale).round().max(1.0) as u32;
+    let height = (rect[3] * scale).round().max(1.0) as u32;
+    // Gradients with the same parameters and raster size share an id
+    let mut hasher = DefaultHasher::new();
+    (center[0].to_bits(), center[1].to_bits(), width, height).hash(&mut hasher);
+    for (t, color) in stops {
+        t.to_bits().hash(&mut hasher);
+        color
+            .iter()
+            .map(|c| c.to_bits())
+            .for_each(|c| c.hash(&mut hasher));
+    }
+    let id = hasher.finish() | 1 << 63;
+    let raster = || rasterize_radial_gradient(center, stops, width, height);
+    if let Some(texture) = textures.texture(id, &raster) {
+        let source = [0.0, 0.0, f64::from(width), f64::from(height)];
+        fill.images(texture, &[(rect, source)]);
+    }
+}
+
+/// Rasterize a radial gradient into an image of the given size
+#[cfg(feature = "graphics")]
+fn rasterize_radial_gradient(
+    center: [f64; 2],
+    stops: &[ColorStop],
+    width: u32,
+    height: u32,
+) -> ImageFill {
+    let (w, h) = (f64::from(width), f64::from(height));
+    let center = [w * center[0], h * center[1]];
+    let radius = rect_polygon([0.0, 0.0, w, h])
+        .iter()
+        .map(|p| ((p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2)).sqrt())
+        .fold(0.0, f64::max);
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let dist = ((f64::from(x) + 0.5 - center[0]).powi(2)
+                + (f64::from(y) + 0.5 - center[1]).powi(2))
+            .sqrt();
+            let t = if radius > 0.0 { dist / radius } else { 0.0 };
+            pixels.push(gradient_color(stops, t as f32));
+        }
+    }
+    ImageFill::new(width, height, pixels).expect("gradient raster size")
+}
+
+/// Get the intersection of two rectangles
+#[cfg(feature = "graphics")]
+fn intersect(a: [f64; 4], b: [f64; 4]) -> Option<[f64; 4]> {
+    let left = a[0].max(b[0]);
+    let top = a[1].max(b[1]);
+    let right = (a[0] + a[2]).min(b[0] + b[2]);
+    let bottom = (a[1] + a[3]).min(b[1] + b[3]);
+    if right > left && bottom > top {
+        Some([left, top, right - left, bottom - top])
+    } else {
+        None
+    }
+}
+
+/// Draw an image into the area
+#[cfg(feature = "graphics")]
+fn draw_image<X, G>(image: &ImageFill, mode: ImageMode, textures: &mut X, fill: &mut Fill<G>)
+where
+    X: TextureSource<Texture = G::Texture>,
+    G: Graphics,
+{
+    let rect = fill.rect;
+    let (width, height) = (image.width, image.height);
+    if width == 0 || height == 0 {
+        return;
+    }
+    let texture = match textures.texture(image.id, &|| image.clone()) {
+        Some(texture) => texture,
+        None => return,
+    };
+    let (w, h) = (f64::from(width), f64::from(height));
+    match mode {
+        ImageMode::Stretch => fill.images(texture, &[(rect, [0.0, 0.0, w, h])]),
+        ImageMode::Tile => {
+            let mut regions = Vec::new();
+            let mut y = rect[1];
+            while y < rect[1] + rect[3] {
+                let mut x = rect[0];
+                while x < rect[0] + rect[2] {
+                    regions.push(([x, y, w, h], [0.0, 0.0, w, h]));
+                    x += w;
+                }
+                y += h;
+            }
+            fill.images(texture, &regions);
+        }
+        ImageMode::NineSlice(left, right, top, bottom) => {
+            let left = left.min(width);
+            let right = right.min(width - left);
+            let top = top.min(height);
+            let bottom = bottom.min(height - top);
+            let (l, r, t, b) = (
+                f64::from(left),
+                f64::from(right),
+                f64::from(top),
+                f64::from(bottom),
+            );
+            let source_columns = [(0.0, l), (l, w - l - r), (w - r, r)];
+            let source_rows = [(0.0, t), (t, h - t - b), (h - b, b)];
+            let dest_columns = [
+                (rect[0], l),
+                (rect[0] + l, (rect[2] - l - r).max(0.0)),
+                (rect[0] + rect[2] - r, r),
+            ];
+            let dest_rows = [
+                (rect[1], t),
+                (rect[1] + t, (rect[3] - t - b).max(0.0)),
+                (rect[1] + rect[3] - b, b),
+            ];
+            let mut regions = Vec::new();
+            for (source_row, dest_row) in source_rows.iter().zip(&dest_rows) {
+                for (source_column, dest_column) in source_columns.iter().zip(&dest_columns) {
+                    if source_column.1 > 0.0 && source_row.1 > 0.0 {
+                        regions.push((
+                            [dest_column.0, dest_row.0, dest_column.1, dest_row.1],
+                            [source_column.0, source_row.0, source_column.1, source_row.1],
+                        ));
+                    }
+                }
+            }
+            fill.images(texture, &regions);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rasterized_shadow() {
+        let shadow = Shadow::new([2.0, 0.0], color::BLACK).blur(4.0);
+        let image = shadow.rasterize([4.0, 4.0, 8.0, 8.0], 0.0, 20, 16).unwrap();
+        let alpha = |x: usize, y: usize| image.pixels[y * 20 + x][3];
+        // Opaque well inside the offset rectangle
+        assert_eq!(alpha(10, 8), 1.0);
+        // Half faded at the edge, and gone past the blur
+        assert!((alpha(14, 8) - 0.5).abs() < 0.2);
+        assert_eq!(alpha(19, 8), 0.0);
+        assert_eq!(alpha(0, 8), 0.0);
+        // Faded around the corners
+        assert!(alpha(14, 12) < alpha(14, 8));
+        // Without blur, the edge is hard
+        let hard = Shadow::new([0.0, 0.0], color::BLACK).spread(1.0);
+        assert_eq!(hard.alpha_at([4.0, 4.0, 8.0, 8.0], 0.0, [3.5, 8.0]), 1.0);
+        assert_eq!(hard.alpha_at([4.0, 4.0, 8.0, 8.0], 0.0, [2.5, 8.0]), 0.0);
+    }
+}
@@ -2,11 +2,16 @@ use std::collections::HashMap;
 
 #[cfg(feature = "graphics")]
 use graphics::{
-    character::CharacterCache, math::Matrix2d, text as draw_text, Graphics, ImageSize, Transformed,
+    character::CharacterCache, draw_state::DrawState, math::Matrix2d, Graphics, ImageSize, Text,
+    Transformed, Viewport,
 };
 use rusttype::{Error, Font, GlyphId, Scale};
+use unicode_linebreak::{linebreaks, BreakOpportunity};
 
+use crate::hyphenation::Hyphenator;
 use crate::math::{Rectangle, Scalar, Vector2, ZeroOneTwo};
+#[cfg(feature = "graphics")]
+use crate::style;
 use crate::Color;
 
 /// A horizantal text justification
@@ -18,13 +23,149 @@ pub enum Justification {
     Centered,
     /// Align on the right
     Right,
+    /// Align on both the left and the right by stretching the
+    /// spaces between words
+    ///
+    /// The last line of each paragraph is aligned on the left.
+    Justified,
+}
+
+/// A vertical text alignment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum VerticalAlignment {
+    /// Align on the top
+    #[default]
+    Top,
+    /// Center align
+    Middle,
+    /// Align on the bottom
+    Bottom,
+    /// Spread the lines' baselines evenly from the top to the bottom
+    ///
+    /// A single line is centered. Text that does not fit is aligned on the top.
+    Distributed,
+}
+
+/// A way of handling text which does not fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TextOverflow {
+    /// Draw all the text, even outside its rectangle
+    #[default]
+    Visible,
+    /// Drop lines past the maximum and cut off text at the edges of its rectangle
+    Clip,
+    /// Like `TextOverflow::Clip`, but end the last line with an ellipsis if
+    /// lines are dropped, and shorten lines which are too wide with an ellipsis
+    Ellipsis,
+    /// Like `TextOverflow::Ellipsis`, but paragraphs are not wrapped, and those
+    /// which are too wide are shortened by replacing their middles with an
+    /// ellipsis. This is useful for file paths.
+    MiddleEllipsis,
+    /// Like `TextOverflow::Clip`, but fade out the last line if lines are
+    /// dropped, and fade out the ends of lines which are too wide
+    Fade,
+}
+
+/// A policy for breaking words which do not fit on a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum WordBreak {
+    /// Never break words, even if they are too long for a line
+    #[default]
+    Never,
+    /// Break words which are too long for a line between any two characters
+    Anywhere,
+    /// Break words at the hyphenation points given by
+    /// `CharacterWidthCache::hyphenation_points` and insert a hyphen.
+    /// Words which are too long for a line and cannot be
+    /// hyphenated are broken anywhere.
+    Hyphenate,
+}
+
+/// A way of handling whitespace in text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum WhiteSpace {
+    /// Collapse runs of whitespace into single spaces and wrap lines
+    #[default]
+    Normal,
+    /// Keep all whitespace and only break lines at mandatory breaks
+    Preserve,
+    /// Keep all whitespace and wrap lines
+    ///
+    /// Whitespace at the end of a wrapped line is dropped.
+    PreserveWrap,
+}
+
+/// The spacing of the tab stops that tabs advance text to
+///
+/// Tabs are only expanded when whitespace is preserved.
+/// Otherwise, they are collapsed like other whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum TabStops<S>
+where
+    S: Scalar,
+{
+    /// Tab stops every given number of pixels
+    Pixels(S),
+    /// Tab stops every given number of space widths
+    Spaces(usize),
 }
 
+impl<S> Default for TabStops<S>
+where
+    S: Scalar,
+{
+    fn default() -> Self {
+        TabStops::Spaces(8)
+    }
+}
+
+impl<S> TabStops<S>
+where
+    S: Scalar,
+{
+    /// Get the distance between tab stops given the width of a space
+    pub fn interval(self, space_width: S) -> S {
+        match self {
+            TabStops::Pixels(pixels) => pixels,
+            TabStops::Spaces(spaces) => S::from(spaces as u32) * space_width,
+        }
+    }
+    /// Changes the type of the scalar
+    pub fn map<U>(self) -> TabStops<U>
+    where
+        U: Scalar + From<S>,
+    {
+        match self {
+            TabStops::Pixels(pixels) => TabStops::Pixels(U::from(pixels)),
+            TabStops::Spaces(spaces) => TabStops::Spaces(spaces),
+        }
+    }
+}
+
+/// The character used to show that text has been shortened
+pub const ELLIPSIS: char = '\u{2026}';
+
 /// Lines that have starting positions
 ///
 /// `V` usually implements `Vector2`
 pub type PositionedLines<V> = Vec<(V, String)>;
 
+/// Lines of words that have starting positions
+///
+/// `V` usually implements `Vector2`
+pub type PositionedWords<V> = Vec<Vec<(V, String)>>;
+
+/// The vertical metrics of lines of text at a font size
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LineMetrics<S> {
+    /// The distance from the baseline to the top of the tallest glyphs
+    pub ascent: S,
+    /// The distance from the baseline to the bottom of the lowest glyphs
+    pub descent: S,
+    /// The distance between the baselines of lines, before line spacing is applied
+    pub line_height: S,
+}
+
 /// A way of resizing text in a rectangle
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Resize {
@@ -45,9 +186,11 @@ where
     S: Scalar,
 {
     /// The font size
-    pub font_size: u32,
+    pub font_size: f32,
     /// The horizantal justification
     pub just: Justification,
+    /// The vertical alignment
+    pub vertical_align: VerticalAlignment,
     /// The spacing between lines. This should usually be somewhere
     /// between `1.0` and `2.0`, but any scalar is valid
     pub line_spacing: S,
@@ -60,6 +203,16 @@ where
     pub color: Color,
     /// The resize strategy
     pub resize: Resize,
+    /// The maximum number of lines
+    pub max_lines: Option<usize>,
+    /// The way of handling text which does not fit
+    pub overflow: TextOverflow,
+    /// The policy for breaking words which do not fit on a line
+    pub word_break: WordBreak,
+    /// The way of handling whitespace
+    pub white_space: WhiteSpace,
+    /// The tab stops used when whitespace is preserved
+    pub tab_stops: TabStops<S>,
 }
 
 impl<S> From<u32> for TextFormat<S>
@@ -67,6 +220,15 @@ where
     S: Scalar,
 {
     fn from(font_size: u32) -> Self {
+        TextFormat::new(font_size as f32)
+    }
+}
+
+impl<S> From<f32> for TextFormat<S>
+where
+    S: Scalar,
+{
+    fn from(font_size: f32) -> Self {
         TextFormat::new(font_size)
     }
 }
@@ -76,15 +238,21 @@ where
     S: Scalar,
 {
     /// Create a default `TextFormat` with the given font size
-    pub fn new(font_size: u32) -> TextFormat<S> {
+    pub fn new(font_size: f32) -> TextFormat<S> {
         TextFormat {
             font_size,
             just: Justification::Left,
+            vertical_align: VerticalAlignment::Top,
             line_spacing: S::ONE,
             first_line_indent: 0,
             lines_indent: 0,
             color: [0.0, 0.0, 0.0, 1.0],
             resize: Resize::NoLarger,
+            max_lines: None,
+            overflow: TextOverflow::Visible,
+            word_break: WordBreak::Never,
+            white_space: WhiteSpace::Normal,
+            tab_stops: TabStops::default(),
         }
     }
     /// Align the `TextFormat` to the left
@@ -102,8 +270,18 @@ where
         self.just = Justification::Right;
         self
     }
+    /// Align the `TextFormat` to both the left and the right
+    pub fn justified(mut self) -> Self {
+        self.just = Justification::Justified;
+        self
+    }
+    /// Set the vertical alignment
+    pub fn vertical_align(mut self, vertical_align: VerticalAlignment) -> Self {
+        self.vertical_align = vertical_align;
+        self
+    }
     /// Set the font size
-    pub fn font_size(mut self, font_size: u32) -> Self {
+    pub fn font_size(mut self, font_size: f32) -> Self {
         self.font_size = font_size;
         self
     }
@@ -120,11 +298,17 @@ where
         TextFormat {
             font_size: self.font_size,
             just: self.just,
+            vertical_align: self.vertical_align,
             line_spacing: U::from(self.line_spacing),
             first_line_indent: self.first_line_indent,
             lines_indent: self.lines_indent,
             color: self.color,
             resize: self.resize,
+            max_lines: self.max_lines,
+            overflow: self.overflow,
+            word_break: self.word_break,
+            white_space: self.white_space,
+            tab_stops: self.tab_stops.map(),
         }
     }
     /// Set the indentation of the first line
@@ -147,10 +331,40 @@ where
         self.resize = resize;
         self
     }
+    /// Set the maximum number of lines
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+    /// Remove the maximum number of lines
+    pub fn no_max_lines(mut self) -> Self {
+        self.max_lines = None;
+        self
+    }
+    /// Set the way of handling text which does not fit
+    pub fn overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    /// Set the policy for breaking words which do not fit on a line
+    pub fn word_break(mut self, word_break: WordBreak) -> Self {
+        self.word_break = word_break;
+        self
+    }
+    /// Set the way of handling whitespace
+    pub fn white_space(mut self, white_space: WhiteSpace) -> Self {
+        self.white_space = white_space;
+        self
+    }
+    /// Set the tab stops
+    pub fn tab_stops(mut self, tab_stops: TabStops<S>) -> Self {
+        self.tab_stops = tab_stops;
+        self
+    }
     /// Change the font size depending on the the resize strategy
     ///
     /// The given max size is not used if the strategy is `Resize::None`
-    pub fn resize_font(mut self, max_size: u32) -> Self {
+    pub fn resize_font(mut self, max_size: f32) -> Self {
         match self.resize {
             Resize::NoLarger => self.font_size = self.font_size.min(max_size),
             Resize::Max => self.font_size = max_size,
@@ -160,6 +374,150 @@ where
     }
 }
 
+/// The smallest difference between font sizes that is
+/// measured separately by `Glyphs` and `fit_max_font_size`
+pub const FONT_SIZE_PRECISION: f32 = 1.0 / 64.0;
+
+/// The font size used for styled text when no style in the tree sets one
+pub const DEFAULT_FONT_SIZE: f32 = 20.0;
+
+/// A partial text format whose unset fields are inherited from parent `Pane`s
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TextStyle<S>
+where
+    S: Scalar,
+{
+    /// The font size
+    pub font_size: Option<f32>,
+    /// The horizantal justification
+    pub just: Option<Justification>,
+    /// The vertical alignment
+    pub vertical_align: Option<VerticalAlignment>,
+    /// The spacing between lines
+    pub line_spacing: Option<S>,
+    /// The color of the text
+    pub color: Option<Color>,
+    /// The resize strategy
+    pub resize: Option<Resize>,
+}
+
+impl<S> Default for TextStyle<S>
+where
+    S: Scalar,
+{
+    fn default() -> Self {
+        TextStyle::new()
+    }
+}
+
+impl<S> From<TextFormat<S>> for TextStyle<S>
+where
+    S: Scalar,
+{
+    fn from(format: TextFormat<S>) -> Self {
+        TextStyle {
+            font_size: Some(format.font_size),
+            just: Some(format.just),
+            vertical_align: Some(format.vertical_align),
+            line_spacing: Some(format.line_spacing),
+            color: Some(format.color),
+            resize: Some(format.resize),
+        }
+    }
+}
+
+impl<S> TextStyle<S>
+where
+    S: Scalar,
+{
+    /// Create a new `TextStyle` which inherits everything
+    pub fn new() -> Self {
+        TextStyle {
+            font_size: None,
+            just: None,
+            vertical_align: None,
+            line_spacing: None,
+            color: None,
+            resize: None,
+        }
+    }
+    /// Align the `TextStyle` to the left
+    pub fn left(mut self) -> Self {
+        self.just = Some(Justification::Left);
+        self
+    }
+    /// Center-align the `TextStyle`
+    pub fn centered(mut self) -> Self {
+        self.just = Some(Justification::Centered);
+        self
+    }
+    /// Align the `TextStyle` to the right
+    pub fn right(mut self) -> Self {
+        self.just = Some(Justification::Right);
+        self
+    }
+    /// Align the `TextStyle` to both the left and the right
+    pub fn justified(mut self) -> Self {
+        self.just = Some(Justification::Justified);
+        self
+    }
+    /// Set the vertical alignment
+    pub fn vertical_align(mut self, vertical_align: VerticalAlignment) -> Self {
+        self.vertical_align = Some(vertical_align);
+        self
+    }
+    /// Set the font size
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+    /// Set the line spacing
+    pub fn line_spacing(mut self, line_spacing: S) -> Self {
+        self.line_spacing = Some(line_spacing);
+        self
+    }
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Set the resize strategy
+    pub fn resize(mut self, resize: Resize) -> Self {
+        self.resize = Some(resize);
+        self
+    }
+    /// Fill in any unset fields with those of a parent style
+    pub fn inherit(self, parent: TextStyle<S>) -> Self {
+        TextStyle {
+            font_size: self.font_size.or(parent.font_size),
+            just: self.just.or(parent.just),
+            vertical_align: self.vertical_align.or(parent.vertical_align),
+            line_spacing: self.line_spacing.or(parent.line_spacing),
+            color: self.color.or(parent.color),
+            resize: self.resize.or(parent.resize),
+        }
+    }
+    /// Override the fields of a `TextFormat` with any that are set in this style
+    pub fn apply(&self, format: TextFormat<S>) -> TextFormat<S> {
+        TextFormat {
+            font_size: self.font_size.unwrap_or(format.font_size),
+            just: self.just.unwrap_or(format.just),
+            vertical_align: self.vertical_align.unwrap_or(format.vertical_align),
+            line_spacing: self.line_spacing.unwrap_or(format.line_spacing),
+            color: self.color.unwrap_or(format.color),
+            resize: self.resize.unwrap_or(format.resize),
+            ..format
+        }
+    }
+    /// Create a `TextFormat` from this style, using the
+    /// defaults of `TextFormat::new` for unset fields
+    ///
+    /// If the font size is not set, `DEFAULT_FONT_SIZE` is used.
+    pub fn format(&self) -> TextFormat<S> {
+        self.apply(TextFormat::new(DEFAULT_FONT_SIZE))
+    }
+}
+
 /// Defines behavior of a cache of character widths.
 ///
 /// In general, determining the width of a character glyphs with a given font size
@@ -169,24 +527,101 @@ pub trait CharacterWidthCache {
     /// The scalar type for the width
     type Scalar: Scalar;
     /// Get the width of a character at a font size
-    fn char_width(&mut self, character: char, font_size: u32) -> Self::Scalar;
+    fn char_width(&mut self, character: char, font_size: f32) -> Self::Scalar;
+    /// Get the byte indices in a word at which it may be hyphenated
+    ///
+    /// This is used by `WordBreak::Hyphenate`. By default, there are none,
+    /// so words are only broken if they are too long for a line.
+    fn hyphenation_points(&mut self, _word: &str) -> Vec<usize> {
+        Vec::new()
+    }
+    /// Get the kerning adjustment between a pair of adjacent characters at a font size
+    ///
+    /// This is added to the width of the first character when the second follows it.
+    /// By default, there is no kerning.
+    fn pair_kerning(&mut self, _first: char, _second: char, _font_size: f32) -> Self::Scalar {
+        Self::Scalar::ZERO
+    }
+    /// Get the distance from the baseline to the top of the tallest glyphs at a font size
+    ///
+    /// By default, this is the font size.
+    fn ascent(&mut self, font_size: f32) -> Self::Scalar {
+        font_size.into()
+    }
+    /// Get the distance from the baseline to the bottom of the lowest glyphs at a font size
+    ///
+    /// This is positive for glyphs that go below the baseline. By default, it is zero.
+    fn descent(&mut self, _font_size: f32) -> Self::Scalar {
+        Self::Scalar::ZERO
+    }
+    /// Get the extra space to leave between lines at a font size
+    ///
+    /// By default, there is none.
+    fn line_gap(&mut self, _font_size: f32) -> Self::Scalar {
+        Self::Scalar::ZERO
+    }
+    /// Get the distance between the baselines of lines at a font size,
+    /// before line spacing is applied
+    fn line_height(&mut self, font_size: f32) -> Self::Scalar {
+        self.ascent(font_size) + self.descent(font_size) + self.line_gap(font_size)
+    }
+    /// Get the ascent, descent, and line height at a font size all at once
+    ///
+    /// Text layout gets the metrics with this once per call. Override it
+    /// if measuring the metrics together is cheaper than one by one.
+    fn line_metrics(&mut self, font_size: f32) -> LineMetrics<Self::Scalar> {
+        LineMetrics {
+            ascent: self.ascent(font_size),
+            descent: self.descent(font_size),
+            line_height: self.line_height(font_size),
+        }
+    }
     /// Get the width of a string at a font_size
-    fn width(&mut self, text: &str, font_size: u32) -> Self::Scalar {
-        text.chars()
-            .map(|c| self.char_width(c, font_size))
-            .fold(Self::Scalar::ZERO, std::ops::Add::add)
+    fn width(&mut self, text: &str, font_size: f32) -> Self::Scalar {
+        let mut width = Self::Scalar::ZERO;
+        let mut prev = None;
+        for c in text.chars() {
+            if let Some(prev) = prev {
+                width = width + self.pair_kerning(prev, c, font_size);
+            }
+            width = width + self.char_width(c, font_size);
+            prev = Some(c);
+        }
+        width
     }
     /// Split a string into a list of lines of text with the given format where no line
-    /// is wider than the given max width. Newlines (`\n`) in the string are respected
+    /// is wider than the given max width
+    ///
+    /// Lines are broken according to the Unicode line breaking algorithm (UAX #14).
+    /// Mandatory breaks, such as newlines (`\n`), are respected. Unless the format
+    /// preserves whitespace, runs of breaking whitespace are collapsed into single spaces.
+    ///
+    /// Lines past the format's maximum are dropped. With `TextOverflow::Ellipsis` or
+    /// `TextOverflow::MiddleEllipsis`, lines are shortened with `ELLIPSIS` as needed.
+    /// With `TextOverflow::MiddleEllipsis`, paragraphs are not wrapped.
     fn format_lines<F>(&mut self, text: &str, max_width: Self::Scalar, format: F) -> Vec<String>
+    where
+        F: Into<TextFormat<Self::Scalar>>,
+    {
+        self.format_paragraphs(text, max_width, format).concat()
+    }
+    /// Perform the calculation of `CharacterWidthCache::format_lines`, but group
+    /// the lines by the paragraphs they belong to
+    fn format_paragraphs<F>(
+        &mut self,
+        text: &str,
+        max_width: Self::Scalar,
+        format: F,
+    ) -> Vec<Vec<String>>
     where
         F: Into<TextFormat<Self::Scalar>>,
     {
         let format = format.into();
-        let mut sized_lines = Vec::new();
+        let mut paragraphs = Vec::new();
         let mut first_line = false;
         // Iterate through lines
-        for line in text.lines() {
+        for segments in split_paragraphs(text) {
+            let mut sized_lines = Vec::new();
             // Initialize a result line
             let mut sized_line = String::new();
             // Apply the indentation
@@ -198,15 +633,84 @@ pub trait CharacterWidthCache {
                 .map(|_| ' ')
                 .collect::<String>();
             sized_line.push_str(&indent);
+            let mut indent_len = indent.len();
             let mut curr_width = self.width(&indent, format.font_size);
             // Iterate through words
-            for word in line.split_whitespace() {
-                // Get the word's width
-                let width = self.width(word, format.font_size);
-                // If the word goes past the max width...
-                if !(curr_width + width < max_width || curr_width == Self::Scalar::ZERO) {
-                    // Pop off the trailing space
-                    sized_line.pop();
+            for segment in segments {
+                let word = segment.trim_end_matches(is_collapsible_space);
+                let space = match format.white_space {
+                    WhiteSpace::Normal if word.is_empty() => continue,
+                    WhiteSpace::Normal if word.len() < segment.len() => " ",
+                    WhiteSpace::Normal => "",
+                    WhiteSpace::Preserve | WhiteSpace::PreserveWrap => &segment[word.len()..],
+                };
+                if word.is_empty() {
+                    curr_width = curr_width + join_kerning(self, &sized_line, space, format);
+                    curr_width = advance(self, space, curr_width, format);
+                    sized_line.push_str(space);
+                    continue;
+                }
+                let hyphenation_points = if format.word_break == WordBreak::Hyphenate {
+                    self.hyphenation_points(word)
+                } else {
+                    Vec::new()
+                };
+                // The start of the part of the word that has not been placed
+                let mut start = 0;
+                while start < word.len() {
+                    let rest = &word[start..];
+                    // Get the word's width
+                    let width = self.width(rest, format.font_size)
+                        + join_kerning(self, &sized_line, rest, format);
+                    // A line which only holds its indentation is empty
+                    let line_empty = sized_line.len() == indent_len;
+                    // If the word fits, push it onto the result line
+                    if curr_width + width < max_width
+                        || line_empty && format.word_break == WordBreak::Never
+                        || format.white_space == WhiteSpace::Preserve
+                        || format.overflow == TextOverflow::MiddleEllipsis
+                    {
+                        curr_width = curr_width + width + join_kerning(self, rest, space, format);
+                        curr_width = advance(self, space, curr_width, format);
+                        sized_line.push_str(rest);
+                        sized_line.push_str(space);
+                        break;
+                    }
+                    // Otherwise, try to break it
+                    let remaining = max_width - curr_width;
+                    let fits_alone = width < max_width;
+                    let hyphen_width = self.char_width('-', format.font_size);
+                    let hyphenated = hyphenation_points
+                        .iter()
+                        .rev()
+                        .filter(|&&i| i > start)
+                        .map(|&i| i - start)
+                        .find(|&i| {
+                            self.width(&rest[..i], format.font_size) + hyphen_width < remaining
+                        });
+                    let piece = match (format.word_break, hyphenated) {
+                        (WordBreak::Never, _) => None,
+                        (WordBreak::Hyphenate, Some(i)) => Some((i, true)),
+                        _ if fits_alone && !line_empty => None,
+                        _ => break_anywhere(self, rest, remaining, line_empty, format.font_size)
+                            .map(|i| (i, false)),
+                    };
+                    if let Some((i, hyphen)) = piece {
+                        sized_line.push_str(&rest[..i]);
+                        if hyphen {
+                            sized_line.push('-');
+                        }
+                        start += i;
+                    }
+                    // Pop off the trailing whitespace
+                    if format.white_space == WhiteSpace::Normal {
+                        if sized_line.ends_with(' ') {
+                            sized_line.pop();
+                        }
+                    } else {
+                        let len = sized_line.trim_end_matches(is_collapsible_space).len();
+                        sized_line.truncate(len);
+                    }
                     // Push the result line onto the result list
                     sized_lines.push(sized_line);
                     // Init next line
@@ -221,19 +725,50 @@ pub trait CharacterWidthCache {
                         .map(|_| ' ')
                         .collect::<String>();
                     sized_line.push_str(&indent);
+                    indent_len = indent.len();
                     curr_width = self.width(&indent, format.font_size);
                 }
-                // Push the word onto the result line
-                sized_line.push_str(word);
-                sized_line.push(' ');
-                curr_width = curr_width + width + self.char_width(' ', format.font_size);
             }
             // Push the result line onto the result list
-            sized_line.pop();
+            if format.white_space == WhiteSpace::Normal && sized_line.ends_with(' ') {
+                sized_line.pop();
+            }
             sized_lines.push(sized_line);
+            paragraphs.push(sized_lines);
             first_line = false;
         }
-        sized_lines
+        let ellipsis = match format.overflow {
+            TextOverflow::Ellipsis | TextOverflow::MiddleEllipsis => true,
+            TextOverflow::Visible | TextOverflow::Clip | TextOverflow::Fade => false,
+        };
+        if ellipsis {
+            for line in paragraphs.iter_mut().flatten() {
+                if advance(self, line, Self::Scalar::ZERO, format) > max_width {
+                    *line = if format.overflow == TextOverflow::MiddleEllipsis {
+                        ellipsize_middle(self, line, max_width, format.font_size)
+                    } else {
+                        ellipsize_end(self, line, max_width, format)
+                    };
+                }
+            }
+        }
+        if let Some(max_lines) = format.max_lines {
+            let mut remaining = max_lines;
+            let mut truncated = false;
+            paragraphs.retain_mut(|paragraph| {
+                truncated |= paragraph.len() > remaining;
+                paragraph.truncate(remaining);
+                remaining -= paragraph.len();
+                !paragraph.is_empty()
+            });
+            if truncated && ellipsis {
+                if let Some(last) = paragraphs.last_mut().and_then(|p| p.last_mut()) {
+                    last.push(ELLIPSIS);
+                    *last = ellipsize_end(self, last, max_width, format);
+                }
+            }
+        }
+        paragraphs
     }
     /// Get the width of the widest line after performing
     /// the calculation of `CharacterWidthCache::format_lines`
@@ -245,35 +780,101 @@ pub trait CharacterWidthCache {
         let lines = self.format_lines(text, max_width, format);
         lines
             .into_iter()
-            .map(|line| self.width(&line, format.font_size))
+            .map(|line| advance(self, &line, Self::Scalar::ZERO, format))
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .unwrap_or(Self::Scalar::ZERO)
     }
     /// Calculate a set of positioned lines of text with the given format
     /// that fit within the given rectangle
+    ///
+    /// Unless the format's overflow is `TextOverflow::Visible`, lines which
+    /// would go past the bottom of the rectangle are dropped.
+    ///
+    /// Lines which contain tabs are split at the tabs into separately
+    /// positioned parts that start at the format's tab stops.
     fn justify_text<R, F>(&mut self, text: &str, rect: R, format: F) -> PositionedLines<R::Vector>
     where
         R: Rectangle<Scalar = Self::Scalar>,
         F: Into<TextFormat<Self::Scalar>>,
     {
         let format = format.into();
-        self.format_lines(text, rect.width(), format)
-            .into_iter()
-            .enumerate()
-            .map(|(i, line)| {
-                let y_offset = rect.top()
-                    + format.font_size.into()
-                    + Self::Scalar::from(i as u32) * format.font_size.into() * format.line_spacing;
-                use self::Justification::*;
-                let line_width = self.width(&line, format.font_size);
-                let x_offset = match format.just {
-                    Left => rect.left(),
-                    Centered => rect.center().x() - line_width / Self::Scalar::TWO,
-                    Right => rect.right() - line_width,
+        let metrics = self.line_metrics(format.font_size);
+        let format = fit_max_lines(metrics, format, rect);
+        let lines = self.format_lines(text, rect.width(), format);
+        let count = lines.len();
+        let mut positioned = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let pos = line_position(self, metrics, i, count, &line, rect, format);
+            if !line.contains('\t') {
+                positioned.push((pos, line));
+                continue;
+            }
+            let mut x = Self::Scalar::ZERO;
+            for part in line.split_inclusive('\t') {
+                let part_text = part.trim_end_matches('\t');
+                if !part_text.is_empty() {
+                    positioned.push((R::Vector::new(pos.x() + x, pos.y()), part_text.into()));
+                }
+                x = advance(self, part, x, format);
+            }
+        }
+        positioned
+    }
+    /// Calculate a set of lines of positioned words with the given format
+    /// that fit within the given rectangle
+    ///
+    /// Unlike `CharacterWidthCache::justify_text`, this stretches the spaces between
+    /// words for `Justification::Justified`.
+    fn justify_words<R, F>(&mut self, text: &str, rect: R, format: F) -> PositionedWords<R::Vector>
+    where
+        R: Rectangle<Scalar = Self::Scalar>,
+        F: Into<TextFormat<Self::Scalar>>,
+    {
+        let format = format.into();
+        let metrics = self.line_metrics(format.font_size);
+        let format = fit_max_lines(metrics, format, rect);
+        let space_width = self.char_width(' ', format.font_size);
+        let paragraphs = self.format_paragraphs(text, rect.width(), format);
+        let count = paragraphs.iter().map(Vec::len).sum();
+        let mut i = 0;
+        let mut words = Vec::new();
+        for paragraph in paragraphs {
+            let paragraph_len = paragraph.len();
+            for (j, line) in paragraph.into_iter().enumerate() {
+                let pos = line_position(self, metrics, i, count, &line, rect, format);
+                i += 1;
+                let indent_len = line.len() - line.trim_start_matches(' ').len();
+                let spaces = line[indent_len..].matches(' ').count();
+                let stretch = if format.just == Justification::Justified
+                    && j + 1 < paragraph_len
+                    && spaces > 0
+                {
+                    (rect.width() - advance(self, &line, Self::Scalar::ZERO, format))
+                        .max(Self::Scalar::ZERO)
+                        / Self::Scalar::from(spaces as u32)
+                } else {
+                    Self::Scalar::ZERO
                 };
-                (R::Vector::new(x_offset, y_offset), line)
-            })
-            .collect()
+                // Positions are relative to the line's start so that tabs line up
+                let mut x = Self::Scalar::from(indent_len as u32) * space_width;
+                let mut line_positions = Vec::new();
+                let mut prev_part = &line[..indent_len];
+                for part in line[indent_len..].split_inclusive([' ', '\t']) {
+                    x = x + join_kerning(self, prev_part, part, format);
+                    prev_part = part;
+                    let word = part.trim_end_matches([' ', '\t']);
+                    if !word.is_empty() {
+                        line_positions.push((R::Vector::new(pos.x() + x, pos.y()), word.into()));
+                    }
+                    x = advance(self, part, x, format);
+                    if part.ends_with(' ') {
+                        x = x + stretch;
+                    }
+                }
+                words.push(line_positions);
+            }
+        }
+        words
     }
     /// Check if text with the given format fits within a rectangle's width
     fn text_fits_horizontal<R, F>(&mut self, text: &str, rect: R, format: F) -> bool
@@ -294,12 +895,13 @@ pub trait CharacterWidthCache {
         if lines.is_empty() {
             return true;
         }
+        let metrics = self.line_metrics(format.font_size);
         let last_line_y = rect.top()
-            + format.font_size.into()
+            + metrics.ascent
             + Self::Scalar::from((lines.len() - 1) as u32)
-                * format.font_size.into()
+                * metrics.line_height
                 * format.line_spacing;
-        last_line_y < rect.bottom()
+        last_line_y + metrics.descent < rect.bottom()
     }
     /// Check if text with the given format fits within a rectangle
     fn text_fits<R, F>(&mut self, text: &str, rect: R, format: F) -> bool
@@ -312,16 +914,42 @@ pub trait CharacterWidthCache {
     }
     /// Determine the maximum font size for text with the given format
     /// that will still allow the text to fit within a rectangle
-    fn fit_max_font_size<R, F>(&mut self, text: &str, rect: R, format: F) -> u32
+    ///
+    /// Text which would be cut off by the format's maximum number of lines or
+    /// overflow handling does not count as fitting. The font size is never less than `1`.
+    ///
+    /// The font size is found by bisection to within `FONT_SIZE_PRECISION`.
+    fn fit_max_font_size<R, F>(&mut self, text: &str, rect: R, format: F) -> f32
     where
         R: Rectangle<Scalar = Self::Scalar>,
         F: Into<TextFormat<Self::Scalar>>,
     {
-        let mut format = format.into();
-        while !self.text_fits(text, rect, format) {
-            format.font_size -= 1;
+        let format = format.into();
+        let fits = |glyphs: &mut Self, font_size: f32| {
+            let fitting = TextFormat {
+                font_size,
+                max_lines: None,
+                overflow: TextOverflow::Visible,
+                ..format
+            };
+            glyphs.text_fits(text, rect, fitting)
+                && format.max_lines.map_or(true, |max_lines| {
+                    glyphs.format_lines(text, rect.width(), fitting).len() <= max_lines
+                })
+        };
+        if format.font_size <= 1.0 || fits(self, format.font_size) {
+            return format.font_size;
+        }
+        let (mut low, mut high) = (1.0, format.font_size);
+        while high - low > FONT_SIZE_PRECISION {
+            let mid = (low + high) / 2.0;
+            if fits(self, mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
         }
-        format.font_size
+        low
     }
     /// Determine the minumum height for a rectangle such that text
     /// with the given format will still fit within the rectangle
@@ -379,14 +1007,279 @@ pub trait CharacterWidthCache {
     }
 }
 
+/// Limit the number of lines of a format to those that fit
+/// in a rectangle's height, unless overflow is visible
+fn fit_max_lines<R>(
+    metrics: LineMetrics<R::Scalar>,
+    mut format: TextFormat<R::Scalar>,
+    rect: R,
+) -> TextFormat<R::Scalar>
+where
+    R: Rectangle,
+{
+    let line_height = metrics.line_height * format.line_spacing;
+    if format.overflow == TextOverflow::Visible || line_height <= R::Scalar::ZERO {
+        return format;
+    }
+    let first_height = metrics.ascent + metrics.descent;
+    let mut fitting = 0;
+    while format
+        .max_lines
+        .map_or(true, |max_lines| fitting < max_lines)
+        && first_height + R::Scalar::from(fitting as u32) * line_height <= rect.height()
+    {
+        fitting += 1;
+    }
+    format.max_lines = Some(fitting);
+    format
+}
+
+/// Split text into paragraphs at mandatory line breaks, and split each paragraph
+/// into segments that end at line break opportunities
+///
+/// Each segment includes any trailing whitespace, but not line terminators.
+fn split_paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = Vec::new();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (end, opportunity) in linebreaks(text) {
+        // The end of the text is always a mandatory break, even right after another
+        if end == start && end == text.len() && segments.is_empty() {
+            break;
+        }
+        let segment = &text[start..end];
+        start = end;
+        match opportunity {
+            BreakOpportunity::Allowed => segments.push(segment),
+            BreakOpportunity::Mandatory => {
+                segments.push(segment.trim_end_matches(is_line_terminator));
+                paragraphs.push(std::mem::take(&mut segments));
+            }
+        }
+    }
+    paragraphs
+}
+
+/// Check if a character ends a line
+fn is_line_terminator(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+/// Check if a character is whitespace that can be collapsed and broken at
+///
+/// Non-breaking spaces are not collapsible.
+fn is_collapsible_space(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{A0}' | '\u{2007}' | '\u{202F}') && !is_line_terminator(c)
+}
+
+/// Get the byte index of the longest start of a word that is narrower than a width
+///
+/// If none of the word fits and the line is empty, its first character is used anyway.
+fn break_anywhere<C>(
+    glyphs: &mut C,
+    word: &str,
+    width: C::Scalar,
+    line_empty: bool,
+    font_size: f32,
+) -> Option<usize>
+where
+    C: CharacterWidthCache + ?Sized,
+{
+    let mut curr_width = C::Scalar::ZERO;
+    let mut end = 0;
+    let mut prev = None;
+    for (i, c) in word.char_indices() {
+        if let Some(prev) = prev {
+            curr_width = curr_width + glyphs.pair_kerning(prev, c, font_size);
+        }
+        curr_width = curr_width + glyphs.char_width(c, font_size);
+        if curr_width >= width {
+            break;
+        }
+        end = i + c.len_utf8();
+        prev = Some(c);
+    }
+    if end == 0 && line_empty {
+        word.chars().next().map(char::len_utf8)
+    } else if end == 0 {
+        None
+    } else {
+        Some(end)
+    }
+}
+
+/// Get the position after some text which starts at a position in a line
+///
+/// Tabs advance the position to the next of the format's tab stops.
+/// Other characters are kerned with the ones before them.
+fn advance<C>(
+    glyphs: &mut C,
+    text: &str,
+    start: C::Scalar,
+    format: TextFormat<C::Scalar>,
+) -> C::Scalar
+where
+    C: CharacterWidthCache + ?Sized,
+{
+    let mut x = start;
+    let mut prev = None;
+    for c in text.chars() {
+        let space_width = glyphs.char_width(' ', format.font_size);
+        let interval = format.tab_stops.interval(space_width);
+        if let Some(prev) = prev.filter(|_| c != '\t') {
+            x = x + glyphs.pair_kerning(prev, c, format.font_size);
+        }
+        prev = Some(c).filter(|&c| c != '\t');
+        if c != '\t' {
+            x = x + glyphs.char_width(c, format.font_size);
+        } else if interval <= C::Scalar::ZERO {
+            x = x + space_width;
+        } else {
+            let mut stop = interval;
+            while stop <= x {
+                stop = stop + interval;
+            }
+            x = stop;
+        }
+    }
+    x
+}
+
+/// Get the kerning between the end of some text and the start of the text that follows it
+fn join_kerning<C>(
+    glyphs: &mut C,
+    before: &str,
+    after: &str,
+    format: TextFormat<C::Scalar>,
+) -> C::Scalar
+where
+    C: CharacterWidthCache + ?Sized,
+{
+    match (before.chars().last(), after.chars().next()) {
+        (Some(first), Some(second)) if first != '\t' && second != '\t' => {
+            glyphs.pair_kerning(first, second, format.font_size)
+        }
+        _ => C::Scalar::ZERO,
+    }
+}
+
+/// Shorten a line so that it and a trailing ellipsis are no wider than a max width
+fn ellipsize_end<C>(
+    glyphs: &mut C,
+    line: &str,
+    max_width: C::Scalar,
+    format: TextFormat<C::Scalar>,
+) -> String
+where
+    C: CharacterWidthCache + ?Sized,
+{
+    let mut line = line.trim_end_matches(ELLIPSIS).to_string();
+    let max_width = max_width - glyphs.char_width(ELLIPSIS, format.font_size);
+    while !line.is_empty() && advance(glyphs, &line, C::Scalar::ZERO, format) > max_width {
+        line.pop();
+    }
+    let mut line = line.trim_end().to_string();
+    line.push(ELLIPSIS);
+    line
+}
+
+/// Shorten a line by replacing its middle with an ellipsis
+/// so that it is no wider than a max width
+fn ellipsize_middle<C>(glyphs: &mut C, line: &str, max_width: C::Scalar, font_size: f32) -> String
+where
+    C: CharacterWidthCache + ?Sized,
+{
+    let chars: Vec<char> = line.chars().collect();
+    let mut width = glyphs.char_width(ELLIPSIS, font_size);
+    let (mut start, mut end) = (0, chars.len());
+    // Alternately take characters from the start and the end while they fit
+    while start < end {
+        let i = if start <= chars.len() - end {
+            start
+        } else {
+            end - 1
+        };
+        let char_width = glyphs.char_width(chars[i], font_size);
+        if width + char_width > max_width {
+            break;
+        }
+        width = width + char_width;
+        if i == start {
+            start += 1;
+        } else {
+            end -= 1;
+        }
+    }
+    let mut shortened: String = chars[..start].iter().collect();
+    shortened.push(ELLIPSIS);
+    shortened.extend(&chars[end..]);
+    shortened
+}
+
+/// Get the starting position of a line of text in a block of lines
+fn line_position<C, R>(
+    glyphs: &mut C,
+    metrics: LineMetrics<C::Scalar>,
+    index: usize,
+    count: usize,
+    line: &str,
+    rect: R,
+    format: TextFormat<C::Scalar>,
+) -> R::Vector
+where
+    C: CharacterWidthCache + ?Sized,
+    R: Rectangle<Scalar = C::Scalar>,
+{
+    let LineMetrics {
+        ascent, descent, ..
+    } = metrics;
+    let line_height = metrics.line_height * format.line_spacing;
+    let index = C::Scalar::from(index as u32);
+    let spare = rect.height()
+        - ascent
+        - descent
+        - C::Scalar::from(count.saturating_sub(1) as u32) * line_height;
+    let spare = spare.max(C::Scalar::ZERO);
+    let y_offset = match format.vertical_align {
+        VerticalAlignment::Top => rect.top() + ascent + index * line_height,
+        VerticalAlignment::Middle => {
+            rect.top() + spare / C::Scalar::TWO + ascent + index * line_height
+        }
+        VerticalAlignment::Bottom => rect.top() + spare + ascent + index * line_height,
+        VerticalAlignment::Distributed if count > 1 => {
+            let distributed_height =
+                (rect.height() - ascent - descent) / C::Scalar::from(count as u32 - 1);
+            rect.top() + ascent + index * distributed_height.max(line_height)
+        }
+        VerticalAlignment::Distributed => rect.top() + spare / C::Scalar::TWO + ascent,
+    };
+    use self::Justification::*;
+    let line_width = advance(glyphs, line, C::Scalar::ZERO, format);
+    let x_offset = match format.just {
+        Left | Justified => rect.left(),
+        Centered => rect.center().x() - line_width / C::Scalar::TWO,
+        Right => rect.right() - line_width,
+    };
+    R::Vector::new(x_offset, y_offset)
+}
+
 /// A basic implememntor for `CharacterWidthCache`
+///
+/// A `Glyphs` can have a chain of fallback fonts. The width of each character
+/// comes from the first font which has a glyph for it. If none of them do,
+/// the replacement character `'\u{FFFD}'` is used instead.
 #[derive(Clone)]
 pub struct Glyphs<'f, S = f64>
 where
     S: Scalar,
 {
     widths: HashMap<(u32, char), S>,
-    font: Font<'f>,
+    kernings: HashMap<(u32, char, char), S>,
+    fonts: Vec<Font<'f>>,
+    hyphenator: Option<Hyphenator>,
 }
 
 impl<'f, S> Glyphs<'f, S>
@@ -395,18 +1288,62 @@ where
 {
     /// Loads a `Glyphs` from an array of font data.
     pub fn from_bytes(bytes: &'f [u8]) -> Result<Glyphs<'f, S>, Error> {
-        Ok(Glyphs {
-            widths: HashMap::new(),
-            font: Font::from_bytes(bytes)?,
-        })
+        Ok(Glyphs::from_font(Font::from_bytes(bytes)?))
     }
     /// Loads a `Glyphs` from a `Font`.
     pub fn from_font(font: Font<'f>) -> Glyphs<'f, S> {
         Glyphs {
             widths: HashMap::new(),
-            font,
+            kernings: HashMap::new(),
+            fonts: vec![font],
+            hyphenator: None,
         }
     }
+    /// Loads a `Glyphs` from a list of fonts in order of preference
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn from_fonts<I>(fonts: I) -> Option<Glyphs<'f, S>>
+    where
+        I: IntoIterator<Item = Font<'f>>,
+    {
+        let mut fonts = fonts.into_iter();
+        let first = fonts.next()?;
+        Some(fonts.fold(Glyphs::from_font(first), Glyphs::with_fallback))
+    }
+    /// Add a fallback font to the end of the chain
+    pub fn with_fallback(mut self, font: Font<'f>) -> Self {
+        self.fonts.push(font);
+        self.widths.clear();
+        self.kernings.clear();
+        self
+    }
+    /// Set the `Hyphenator` used for `WordBreak::Hyphenate`
+    pub fn with_hyphenator(mut self, hyphenator: Hyphenator) -> Self {
+        self.hyphenator = Some(hyphenator);
+        self
+    }
+    /// Get the fonts in order of preference
+    pub fn fonts(&self) -> &[Font<'f>] {
+        &self.fonts
+    }
+    /// Get the index of the font used for a character
+    ///
+    /// This is the first font which has a glyph for the character. If there is none,
+    /// it is the first font which has a glyph for `'\u{FFFD}'`, or else the first font.
+    pub fn font_index(&self, character: char) -> usize {
+        let has_glyph = |c| {
+            self.fonts
+                .iter()
+                .position(|font: &Font| font.glyph(c).id() != GlyphId(0))
+        };
+        has_glyph(character)
+            .or_else(|| has_glyph('\u{FFFD}'))
+            .unwrap_or(0)
+    }
+    /// Get the font used for a character
+    pub fn font(&self, character: char) -> &Font<'f> {
+        &self.fonts[self.font_index(character)]
+    }
 }
 
 impl<'f, S> CharacterWidthCache for Glyphs<'f, S>
@@ -414,37 +1351,139 @@ where
     S: Scalar,
 {
     type Scalar = S;
-    fn char_width(&mut self, character: char, font_size: u32) -> Self::Scalar {
-        let font = &self.font;
-        *self
-            .widths
-            .entry((font_size, character))
-            .or_insert_with(|| {
-                let scale = Scale::uniform(font_size as f32);
-                let glyph = font.glyph(character).scaled(scale);
-                let glyph = if glyph.id() == GlyphId(0) && glyph.shape().is_none() {
-                    font.glyph('\u{FFFD}').scaled(scale)
-                } else {
-                    glyph
-                };
-                glyph.h_metrics().advance_width.into()
-            })
+    fn hyphenation_points(&mut self, word: &str) -> Vec<usize> {
+        self.hyphenator
+            .as_ref()
+            .map(|hyphenator| hyphenator.hyphenate(word))
+            .unwrap_or_default()
+    }
+    fn char_width(&mut self, character: char, font_size: f32) -> Self::Scalar {
+        let key = (size_key(font_size), character);
+        if let Some(&width) = self.widths.get(&key) {
+            return width;
+        }
+        let font = self.font(character);
+        let glyph = font.glyph(character);
+        let glyph = if glyph.id() == GlyphId(0) {
+            font.glyph('\u{FFFD}')
+        } else {
+            glyph
+        };
+        let width: S = glyph
+            .scaled(Scale::uniform(font_size))
+            .h_metrics()
+            .advance_width
+            .into();
+        self.widths.insert(key, width);
+        width
+    }
+    fn pair_kerning(&mut self, first: char, second: char, font_size: f32) -> Self::Scalar {
+        let key = (size_key(font_size), first, second);
+        if let Some(&kerning) = self.kernings.get(&key) {
+            return kerning;
+        }
+        // Characters from different fonts are not kerned
+        let index = self.font_index(first);
+        let kerning: S = if index == self.font_index(second) {
+            self.fonts[index]
+                .pair_kerning(Scale::uniform(font_size), first, second)
+                .into()
+        } else {
+            S::ZERO
+        };
+        self.kernings.insert(key, kerning);
+        kerning
+    }
+    fn ascent(&mut self, font_size: f32) -> Self::Scalar {
+        self.fonts[0]
+            .v_metrics(Scale::uniform(font_size))
+            .ascent
+            .into()
     }
+    fn descent(&mut self, font_size: f32) -> Self::Scalar {
+        // Rusttype's descent is negative for glyphs below the baseline
+        (-self.fonts[0].v_metrics(Scale::uniform(font_size)).descent).into()
+    }
+    fn line_gap(&mut self, font_size: f32) -> Self::Scalar {
+        self.fonts[0]
+            .v_metrics(Scale::uniform(font_size))
+            .line_gap
+            .into()
+    }
+}
+
+/// Quantize a font size for use as a cache key
+fn size_key(font_size: f32) -> u32 {
+    (font_size / FONT_SIZE_PRECISION).round() as u32
 }
 
+/// Characters whose glyphs are measured to find the ascent and
+/// descent of fonts drawn with `piston2d-graphics`
+#[cfg(feature = "graphics")]
+const METRIC_CHARACTERS: &str = "ÁÅbdfghjklpqty|";
+
+/// Measure the ascent and descent of a font from the glyphs of `METRIC_CHARACTERS`
+///
+/// Returns `None` if none of the glyphs could be loaded.
+#[cfg(feature = "graphics")]
+fn measure_glyph_extents<C>(cache: &mut C, font_size: f32) -> Option<(f64, f64)>
+where
+    C: CharacterCache,
+{
+    let font_size = font_size.round() as u32;
+    METRIC_CHARACTERS
+        .chars()
+        .filter_map(|c| {
+            let character = cache.character(font_size, c).ok()?;
+            Some((character.top(), character.atlas_size[1] - character.top()))
+        })
+        .fold(None, |extents, (ascent, descent)| match extents {
+            Some((max_ascent, max_descent)) => {
+                Some((ascent.max(max_ascent), descent.max(max_descent)))
+            }
+            None => Some((ascent, descent)),
+        })
+}
+
+/// Font sizes are rounded to whole numbers, as they are when
+/// text is drawn with `piston2d-graphics`
+///
+/// `CharacterCache` does not expose font metrics, so the ascent and descent
+/// are measured from the glyphs of some tall and low characters. The line gap is `0`.
 #[cfg(feature = "graphics")]
 impl<C> CharacterWidthCache for C
 where
     C: CharacterCache,
 {
     type Scalar = f64;
-    fn char_width(&mut self, character: char, font_size: u32) -> Self::Scalar {
+    fn char_width(&mut self, character: char, font_size: f32) -> Self::Scalar {
+        let font_size = font_size.round() as u32;
         if let Ok(texture) = <Self as CharacterCache>::character(self, font_size, character) {
             texture.advance_size.x()
         } else {
             panic!("CharacterWidthCache::character returned Err")
         }
     }
+    fn ascent(&mut self, font_size: f32) -> Self::Scalar {
+        self.line_metrics(font_size).ascent
+    }
+    fn descent(&mut self, font_size: f32) -> Self::Scalar {
+        self.line_metrics(font_size).descent
+    }
+    fn line_height(&mut self, font_size: f32) -> Self::Scalar {
+        self.line_metrics(font_size).line_height
+    }
+    fn line_metrics(&mut self, font_size: f32) -> LineMetrics<Self::Scalar> {
+        let (ascent, descent) = measure_glyph_extents(self, font_size)
+            .map_or((f64::from(font_size), 0.0), |(ascent, descent)| {
+                (ascent, descent.max(0.0))
+            });
+        LineMetrics {
+            ascent,
+            descent,
+            line_height: ascent + descent,
+        }
+    }
 }
 
 /// Draw justified text to something using the `piston2d-graphics` crate
@@ -466,16 +1505,204 @@ where
     C: CharacterCache<Texture = T>,
     G: Graphics<Texture = T>,
 {
-    let format = format.into();
-    for (pos, line) in glyphs.justify_text(text, rect, format.map_line_spacing::<f64>()) {
-        draw_text(
-            format.color,
-            format.font_size,
-            &line,
+    let format = format.into().map_line_spacing::<f64>();
+    let words = glyphs.justify_words(text, rect, format);
+    draw_words(
+        &words,
+        text,
+        rect,
+        format,
+        glyphs,
+        &DrawState::default(),
+        transform,
+        None,
+        graphics,
+    )
+}
+
+/// Draw lines of words which have already been positioned in a rectangle,
+/// cutting off or fading out text according to the format's overflow
+#[cfg(feature = "graphics")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_words<R, T, C, G>(
+    words: &PositionedWords<R::Vector>,
+    text: &str,
+    rect: R,
+    format: TextFormat<f64>,
+    glyphs: &mut C,
+    draw_state: &DrawState,
+    transform: Matrix2d,
+    viewport: Option<Viewport>,
+    graphics: &mut G,
+) -> Result<(), C::Error>
+where
+    R: Rectangle<Scalar = f64>,
+    T: ImageSize,
+    C: CharacterCache<Texture = T>,
+    G: Graphics<Texture = T>,
+{
+    let draw_state = match format.overflow {
+        TextOverflow::Visible => *draw_state,
+        _ => draw_state.scissor(style::scissor(
+            rect,
+            transform,
+            viewport,
+            draw_state.scissor,
+        )),
+    };
+    if format.overflow != TextOverflow::Fade {
+        return draw_lines(
+            &words.concat(),
+            format,
             glyphs,
+            &draw_state,
+            transform,
+            graphics,
+        );
+    }
+    let untruncated = TextFormat {
+        max_lines: None,
+        overflow: TextOverflow::Visible,
+        ..format
+    };
+    let dropped = glyphs.format_lines(text, rect.width(), untruncated).len() > words.len();
+    let fade_width = f64::from(format.font_size) * 2.0;
+    for (i, line) in words.iter().enumerate() {
+        let fade_line = dropped && i + 1 == words.len();
+        let line_end = line.last().map_or(rect.left(), |(pos, word)| {
+            pos.x() + CharacterWidthCache::width(glyphs, word, format.font_size)
+        });
+        let fade_end = line_end > rect.right();
+        if !fade_line && !fade_end {
+            draw_lines(line, format, glyphs, &draw_state, transform, graphics)?;
+            continue;
+        }
+        // Draw each character with its own alpha
+        for (pos, word) in line {
+            let mut x = pos.x();
+            for c in word.chars() {
+                let width = CharacterWidthCache::char_width(glyphs, c, format.font_size);
+                let mut alpha: f64 = 1.0;
+                if fade_line {
+                    alpha = alpha.min((rect.right() - x) / rect.width());
+                }
+                if fade_end {
+                    alpha = alpha.min((rect.right() - x - width) / fade_width);
+                }
+                let mut color = format.color;
+                color[3] *= alpha.clamp(0.0, 1.0) as f32;
+                Text::new_color(color, format.font_size.round() as u32).draw(
+                    c.encode_utf8(&mut [0; 4]),
+                    glyphs,
+                    &draw_state,
+                    transform.trans(x, pos.y()),
+                    graphics,
+                )?;
+                x += width;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Draw lines or words of text which have already been positioned
+#[cfg(feature = "graphics")]
+pub(crate) fn draw_lines<V, T, C, G>(
+    lines: &[(V, String)],
+    format: TextFormat<f64>,
+    glyphs: &mut C,
+    draw_state: &DrawState,
+    transform: Matrix2d,
+    graphics: &mut G,
+) -> Result<(), C::Error>
+where
+    V: Vector2<Scalar = f64>,
+    T: ImageSize,
+    C: CharacterCache<Texture = T>,
+    G: Graphics<Texture = T>,
+{
+    let text = Text::new_color(format.color, format.font_size.round() as u32);
+    for (pos, line) in lines {
+        text.draw(
+            line,
+            glyphs,
+            draw_state,
             transform.trans(pos.x(), pos.y()),
             graphics,
         )?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cache where every character is one unit wide
+    struct Monospace;
+
+    impl CharacterWidthCache for Monospace {
+        type Scalar = f64;
+        fn char_width(&mut self, _character: char, _font_size: f32) -> f64 {
+            1.0
+        }
+    }
+
+    #[test]
+    fn indented_overflow() {
+        let text = "abcdefghijklmnop";
+        for &word_break in &[WordBreak::Never, WordBreak::Anywhere, WordBreak::Hyphenate] {
+            let format = TextFormat::new(10.0).lines_indent(2).word_break(word_break);
+            let lines = Monospace.format_lines(text, 10.0, format);
+            assert!(lines.iter().all(|line| line.starts_with("  ")));
+            assert_eq!(
+                lines.iter().map(|line| line.trim()).collect::<String>(),
+                text
+            );
+            if word_break != WordBreak::Never {
+                assert!(lines.iter().all(|line| line.len() < 10));
+            }
+        }
+    }
+
+    #[test]
+    fn middle_ellipsis_keeps_both_ends() {
+        let format = TextFormat::new(10.0)
+            .overflow(TextOverflow::MiddleEllipsis)
+            .max_lines(1);
+        let lines = Monospace.format_lines("/usr/local/share/file.txt", 10.0, format);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("/usr"));
+        assert!(lines[0].ends_with(".txt"));
+        assert!(lines[0].contains(ELLIPSIS));
+    }
+
+    /// A cache which counts how often its metrics are measured
+    struct Counting(usize);
+
+    impl CharacterWidthCache for Counting {
+        type Scalar = f64;
+        fn char_width(&mut self, _character: char, _font_size: f32) -> f64 {
+            1.0
+        }
+        fn line_metrics(&mut self, font_size: f32) -> LineMetrics<f64> {
+            self.0 += 1;
+            LineMetrics {
+                ascent: font_size.into(),
+                descent: 0.0,
+                line_height: font_size.into(),
+            }
+        }
+    }
+
+    #[test]
+    fn metrics_are_measured_once_per_layout() {
+        let mut glyphs = Counting(0);
+        let format = TextFormat::new(1.0).overflow(TextOverflow::Clip);
+        let lines = glyphs.justify_text("a b c d e f", [0.0, 0.0, 2.0, 10.0], format);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(glyphs.0, 1);
+        glyphs.justify_words("a b c d e f", [0.0, 0.0, 2.0, 10.0], format);
+        assert_eq!(glyphs.0, 2);
+    }
+}
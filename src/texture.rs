@@ -0,0 +1,92 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use graphics::ImageSize;
+use texture::{CreateTexture, Format, TextureSettings};
+
+use crate::style::ImageFill;
+
+/// A source of the textures used to draw image backgrounds and radial gradients
+pub trait TextureSource {
+    /// The texture type
+    type Texture: ImageSize;
+    /// Get the texture with the given id, creating it from an image if there is none
+    ///
+    /// An id always refers to the same pixels, so textures can be reused
+    /// for as long as the source keeps them.
+    fn texture(&mut self, id: u64, image: &dyn Fn() -> ImageFill) -> Option<&Self::Texture>;
+}
+
+/// A `TextureSource` which creates textures with a factory and keeps them
+///
+/// Textures are kept until the cache is cleared, so it should be
+/// cleared when images are no longer used.
+pub struct TextureCache<F, T> {
+    factory: F,
+    settings: TextureSettings,
+    textures: HashMap<u64, T>,
+}
+
+impl<F, T> TextureCache<F, T> {
+    /// Create a new `TextureCache` which creates textures with the given factory
+    pub fn new(factory: F) -> Self {
+        TextureCache {
+            factory,
+            settings: TextureSettings::new(),
+            textures: HashMap::new(),
+        }
+    }
+    /// Set the settings used to create textures
+    pub fn with_settings(mut self, settings: TextureSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+    /// Get the factory
+    pub fn factory(&mut self) -> &mut F {
+        &mut self.factory
+    }
+    /// Remove all of the cached textures
+    pub fn clear(&mut self) {
+        self.textures.clear();
+    }
+}
+
+impl<F, T> TextureSource for TextureCache<F, T>
+where
+    T: CreateTexture<F>,
+{
+    type Texture = T;
+    fn texture(&mut self, id: u64, image: &dyn Fn() -> ImageFill) -> Option<&T> {
+        if !self.textures.contains_key(&id) {
+            let image = image();
+            let texture = T::create(
+                &mut self.factory,
+                Format::Rgba8,
+                &image.to_rgba8(),
+                [image.width(), image.height()],
+                &self.settings,
+            )
+            .ok()?;
+            self.textures.insert(id, texture);
+        }
+        self.textures.get(&id)
+    }
+}
+
+/// A `TextureSource` which has no textures
+pub(crate) struct NoTextures<T>(PhantomData<T>);
+
+impl<T> NoTextures<T> {
+    pub(crate) fn new() -> Self {
+        NoTextures(PhantomData)
+    }
+}
+
+impl<T> TextureSource for NoTextures<T>
+where
+    T: ImageSize,
+{
+    type Texture = T;
+    fn texture(&mut self, _id: u64, _image: &dyn Fn() -> ImageFill) -> Option<&T> {
+        None
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::math::Scalar;
+use crate::style::{Background, Border};
+use crate::text::TextStyle;
+use crate::Color;
+
+/// A way of selecting which `Pane`s a `Style` applies to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Selector {
+    /// Select `Pane`s with the given class
+    Class(String),
+    /// Select `Pane`s with the given name
+    Name(String),
+}
+
+/// A set of properties to apply to a `Pane`
+///
+/// Properties which are not set leave the `Pane`'s existing values unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style<S>
+where
+    S: Scalar,
+{
+    /// The color
+    pub color: Option<Color>,
+    /// The background
+    pub background: Option<Background<S>>,
+    /// The border
+    pub border: Option<Border<S>>,
+    /// The margin
+    pub margin: Option<S>,
+    /// The text style
+    pub text_style: Option<TextStyle<S>>,
+}
+
+impl<S> Default for Style<S>
+where
+    S: Scalar,
+{
+    fn default() -> Self {
+        Style::new()
+    }
+}
+
+impl<S> Style<S>
+where
+    S: Scalar,
+{
+    /// Create a new `Style` which sets nothing
+    pub fn new() -> Self {
+        Style {
+            color: None,
+            background: None,
+            border: None,
+            margin: None,
+            text_style: None,
+        }
+    }
+    /// Set the color
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Set the background
+    pub fn background(mut self, background: Background<S>) -> Self {
+        self.background = Some(background);
+        self
+    }
+    /// Set the border
+    pub fn border(mut self, border: Border<S>) -> Self {
+        self.border = Some(border);
+        self
+    }
+    /// Set the margin
+    pub fn margin(mut self, margin: S) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+    /// Set the text style
+    pub fn text_style(mut self, text_style: TextStyle<S>) -> Self {
+        self.text_style = Some(text_style);
+        self
+    }
+    /// Override any properties of this style with those set in another
+    pub fn merge(mut self, other: &Style<S>) -> Self {
+        if other.color.is_some() {
+            self.color = other.color;
+        }
+        if other.background.is_some() {
+            self.background = other.background.clone();
+        }
+        if other.border.is_some() {
+            self.border = other.border;
+        }
+        if other.margin.is_some() {
+            self.margin = other.margin;
+        }
+        if other.text_style.is_some() {
+            self.text_style = other.text_style;
+        }
+        self
+    }
+}
+
+/// A map of `Selector`s to the `Style`s of the `Pane`s they select
+///
+/// When a theme is applied to a `Pane`, the styles of its classes are
+/// applied in the order the `Pane` lists them, followed by the style of its name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme<S>
+where
+    S: Scalar,
+{
+    styles: HashMap<Selector, Style<S>>,
+}
+
+impl<S> Default for Theme<S>
+where
+    S: Scalar,
+{
+    fn default() -> Self {
+        Theme::new()
+    }
+}
+
+impl<S> Theme<S>
+where
+    S: Scalar,
+{
+    /// Create a new empty `Theme`
+    pub fn new() -> Self {
+        Theme {
+            styles: HashMap::new(),
+        }
+    }
+    /// Set the style for a selector
+    pub fn with_style(mut self, selector: Selector, style: Style<S>) -> Self {
+        self.styles.insert(selector, style);
+        self
+    }
+    /// Set the style for a class
+    pub fn with_class<T>(self, class: T, style: Style<S>) -> Self
+    where
+        T: Into<String>,
+    {
+        self.with_style(Selector::Class(class.into()), style)
+    }
+    /// Set the style for a name
+    pub fn with_name<T>(self, name: T, style: Style<S>) -> Self
+    where
+        T: Into<String>,
+    {
+        self.with_style(Selector::Name(name.into()), style)
+    }
+    /// Get the style for a selector
+    pub fn style(&self, selector: &Selector) -> Option<&Style<S>> {
+        self.styles.get(selector)
+    }
+    /// Get the combined style for a `Pane` with the given classes and name
+    pub fn resolve<'a, I>(&self, classes: I, name: Option<&str>) -> Style<S>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut style = Style::new();
+        for class in classes {
+            if let Some(class_style) = self.style(&Selector::Class(class.into())) {
+                style = style.merge(class_style);
+            }
+        }
+        if let Some(name_style) = name.and_then(|name| self.style(&Selector::Name(name.into()))) {
+            style = style.merge(name_style);
+        }
+        style
+    }
+}
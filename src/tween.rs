@@ -0,0 +1,157 @@
+use crate::math::Rectangle;
+use crate::{color, Color};
+
+/// A snapshot of the rectangles and colors of a `Pane` tree
+///
+/// Layouts can be interpolated to animate a transition between
+/// two states of a `Pane`. Use `Pane::layout` to take a snapshot
+/// and `Pane::with_layout` to apply one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout<R>
+where
+    R: Rectangle,
+{
+    /// The `Pane`'s rectangle
+    pub rect: R,
+    /// The `Pane`'s color
+    pub color: Color,
+    /// The layouts of the `Pane`'s children
+    pub children: Vec<Layout<R>>,
+}
+
+impl<R> Layout<R>
+where
+    R: Rectangle,
+{
+    /// Linearly interpolate between this layout and another
+    ///
+    /// A `t` of `0` gives this layout and a `t` of `1` gives the other.
+    /// If the trees have different shapes, children that only exist
+    /// in the other layout are taken from it as they are.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Layout {
+            rect: self.rect.lerp(other.rect, t.into()),
+            color: color::lerp(self.color, other.color, t),
+            children: other
+                .children
+                .iter()
+                .enumerate()
+                .map(|(i, child)| match self.children.get(i) {
+                    Some(from) => from.lerp(child, t),
+                    None => child.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An easing curve for animating a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Start slow and speed up
+    QuadIn,
+    /// Start fast and slow down
+    QuadOut,
+    /// Start slow, speed up, and slow down
+    QuadInOut,
+    /// Start slow and speed up sharply
+    CubicIn,
+    /// Start fast and slow down sharply
+    CubicOut,
+    /// Start slow, speed up sharply, and slow down sharply
+    CubicInOut,
+}
+
+impl Easing {
+    /// Map a linear progress value in the range `[0, 1]` onto the curve
+    pub fn apply(self, t: f32) -> f32 {
+        use self::Easing::*;
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Linear => t,
+            QuadIn => t * t,
+            QuadOut => t * (2.0 - t),
+            QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            CubicIn => t * t * t,
+            CubicOut => {
+                let u = t - 1.0;
+                u * u * u + 1.0
+            }
+            CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 * t - 2.0;
+                    0.5 * u * u * u + 1.0
+                }
+            }
+        }
+    }
+}
+
+/// An animated transition between two `Layout`s
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<R>
+where
+    R: Rectangle,
+{
+    from: Layout<R>,
+    to: Layout<R>,
+    duration: f64,
+    elapsed: f64,
+    easing: Easing,
+}
+
+impl<R> Tween<R>
+where
+    R: Rectangle,
+{
+    /// Create a new `Tween` which takes the given number of seconds to complete
+    pub fn new(from: Layout<R>, to: Layout<R>, duration: f64) -> Self {
+        Tween {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+            easing: Easing::default(),
+        }
+    }
+    /// Set the easing curve
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+    /// Advance the `Tween` by some number of seconds
+    pub fn update(&mut self, dt: f64) {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+    }
+    /// Get the linear progress of the `Tween` in the range `[0, 1]`
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration) as f32
+        } else {
+            1.0
+        }
+    }
+    /// Check if the `Tween` has completed
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+    /// Get the in-between layout at the `Tween`'s current progress
+    pub fn layout(&self) -> Layout<R> {
+        self.from.lerp(&self.to, self.easing.apply(self.progress()))
+    }
+    /// Get the layout the `Tween` ends at
+    pub fn target(&self) -> &Layout<R> {
+        &self.to
+    }
+}